@@ -0,0 +1,52 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use game_of_life::board::Board;
+use game_of_life::config::Backend;
+
+/// The width and height of the board
+const SIZE: u64 = 512;
+
+/// The number of generations computed per iteration
+const GENERATIONS: usize = 100;
+
+/// Create a board filled with a random soup, always the same one
+fn soup(backend: Backend) -> Board {
+    let board = Board::new();
+
+    board.create_board(SIZE, SIZE).unwrap();
+    board.randomize(0.35, Some(42));
+    board.set_backend(backend);
+
+    board
+}
+
+/// Compare the backends on the same soup, which stays dense over the first generations
+fn backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("512x512 soup, 100 generations");
+
+    group.sample_size(10);
+
+    for backend in [Backend::Cells, Backend::Bitset, Backend::Simulation] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{backend:?}")),
+            &backend,
+            |b, &backend| {
+                b.iter_batched(
+                    || soup(backend),
+                    |board| {
+                        for _ in 0..GENERATIONS {
+                            board.step();
+                        }
+
+                        board
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, backends);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use game_of_life::config::BoundaryMode;
+use game_of_life::hashlife::HashlifeBoard;
+use game_of_life::patterns::Pattern;
+use game_of_life::rule::Rule;
+use game_of_life::simulation;
+use std::collections::HashSet;
+
+/// The distance of the gun from the edges of the naive board, out of reach of its gliders
+const MARGIN: u64 = 2_048;
+
+/// Advance a Gosper glider gun with Hashlife and with the naive engine, `simulation::step` one generation at a time
+fn hashlife_vs_naive(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Gosper glider gun");
+
+    group.sample_size(10);
+
+    let gun = Pattern::GosperGliderGun.cells();
+
+    for generations in [256, 1_024, 4_096] {
+        group.bench_with_input(
+            BenchmarkId::new("hashlife", generations),
+            &generations,
+            |b, &generations| {
+                b.iter(|| {
+                    let cells: Vec<(i64, i64)> =
+                        gun.iter().map(|&(x, y)| (x as i64, y as i64)).collect();
+                    let board = HashlifeBoard::new(Rule::default(), &cells).unwrap();
+
+                    board.advance(generations).unwrap();
+
+                    board.population()
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("naive", generations),
+            &generations,
+            |b, &generations| {
+                b.iter(|| {
+                    let mut cells: HashSet<(u64, u64)> =
+                        gun.iter().map(|&(x, y)| (x + MARGIN, y + MARGIN)).collect();
+
+                    for _ in 0..generations {
+                        cells = simulation::step(
+                            &cells,
+                            2 * MARGIN,
+                            2 * MARGIN,
+                            BoundaryMode::Dead,
+                            &Rule::default(),
+                        );
+                    }
+
+                    cells.len()
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, hashlife_vs_naive);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use game_of_life::board::Board;
+use game_of_life::config::Backend;
+use game_of_life::patterns::Pattern;
+
+/// The width and height of the board
+const SIZE: u64 = 256;
+
+/// Create a board with a Gosper glider gun, computed by the cells backend
+fn gun() -> Board {
+    let board = Board::new();
+
+    board.create_board(SIZE, SIZE).unwrap();
+    board.set_backend(Backend::Cells);
+    board.place_pattern(Pattern::GosperGliderGun, 10, 10);
+
+    board
+}
+
+/// Compare the computation of a generation following another one, which only goes through the cells changed
+/// by the previous one and their neighbors, with one following an edit, which checks every cell of the board.
+/// The generation is computed without being committed, so every iteration computes the same one.
+fn relevant_cells(c: &mut Criterion) {
+    let mut group = c.benchmark_group("256x256 Gosper glider gun, next generation");
+
+    let board = gun();
+
+    for _ in 0..50 {
+        board.step();
+    }
+
+    group.bench_function("after a generation", |b| {
+        b.iter(|| board.compute_next_generation());
+    });
+
+    // Toggled twice, the cell is left as it was, but the changes of the last generation are forgotten
+    board.toggle_at(SIZE - 1, SIZE - 1);
+    board.toggle_at(SIZE - 1, SIZE - 1);
+
+    group.bench_function("after an edit", |b| {
+        b.iter(|| board.compute_next_generation());
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, relevant_cells);
+criterion_main!(benches);
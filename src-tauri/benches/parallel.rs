@@ -0,0 +1,51 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use game_of_life::board::Board;
+use game_of_life::parallel::SERIAL_THRESHOLD;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// The widths of the square boards, around the side of a board of `SERIAL_THRESHOLD` cells
+const SIZES: [u64; 7] = [32, 64, 96, 128, 192, 256, 512];
+
+/// Create a square board filled with a random soup, always the same one
+fn soup(size: u64) -> Board {
+    let board = Board::new();
+
+    board.create_board(size, size).unwrap();
+    board.randomize(0.35, Some(42));
+
+    board
+}
+
+/// Compare a generation computed on a single thread with one computed on one thread per CPU, for each size.
+/// Inside a pool, `parallel::run` runs the work as is, so the pool decides how many threads it gets.
+/// The sizes where the two cross show where `SERIAL_THRESHOLD` belongs on this hardware.
+fn serial_vs_parallel(c: &mut Criterion) {
+    let pools: [(&str, ThreadPool); 2] = [
+        (
+            "serial",
+            ThreadPoolBuilder::new().num_threads(1).build().unwrap(),
+        ),
+        ("parallel", ThreadPoolBuilder::new().build().unwrap()),
+    ];
+
+    let mut group = c.benchmark_group(format!(
+        "one generation, serial threshold at {SERIAL_THRESHOLD} cells"
+    ));
+
+    for size in SIZES {
+        for (name, pool) in &pools {
+            group.bench_with_input(BenchmarkId::new(*name, size * size), &size, |b, &size| {
+                b.iter_batched(
+                    || soup(size),
+                    |board| pool.install(|| board.step()),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, serial_vs_parallel);
+criterion_main!(benches);
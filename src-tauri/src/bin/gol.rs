@@ -0,0 +1,248 @@
+//! A headless runner of the Game of Life, e.g. for scripts and CI.
+//!
+//! Usage: `gol <pattern> [--steps N] [--format rle|cells|count]`
+//!
+//! The pattern is read from a file in the plaintext format if its extension is `.cells`,
+//! and in the RLE format otherwise. It is advanced by `N` generations (0 by default) of Conway's Game of Life
+//! on an unbounded plane, and the final alive cells are printed, cropped to their bounding box,
+//! or their number with `--format count`.
+
+use game_of_life::config::BoundaryMode;
+use game_of_life::rule::Rule;
+use game_of_life::{plaintext, rle, simulation};
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+/// The usage of the command, printed on invalid arguments
+const USAGE: &str = "Usage: gol <pattern> [--steps N] [--format rle|cells|count]";
+
+/// An enum representing how the final alive cells are printed
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    /// The pattern in the RLE format
+    Rle,
+
+    /// The pattern in the plaintext format
+    Cells,
+
+    /// The number of alive cells
+    Count,
+}
+
+/// A struct representing the parsed arguments of the command
+#[derive(Debug)]
+struct Args {
+    /// The path of the pattern file
+    path: String,
+
+    /// The number of generations to compute
+    steps: u64,
+
+    /// How the final alive cells are printed
+    format: Format,
+}
+
+/// Parse the arguments of the command, without the name of the program
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut path = None;
+    let mut steps = 0;
+    let mut format = Format::Rle;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--steps" => {
+                let value = args.next().ok_or("Missing value for `--steps`")?;
+
+                steps = value
+                    .parse()
+                    .map_err(|_| format!("Invalid number of steps `{value}`"))?;
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("rle") => Format::Rle,
+                    Some("cells") => Format::Cells,
+                    Some("count") => Format::Count,
+                    Some(value) => return Err(format!("Unknown format `{value}`")),
+                    None => return Err("Missing value for `--format`".to_string()),
+                };
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown option `{arg}`")),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    Ok(Args {
+        path: path.ok_or("Missing the pattern file")?,
+        steps,
+        format,
+    })
+}
+
+/// Read the pattern, run it and format the result
+fn run(args: &Args) -> Result<String, String> {
+    let input = fs::read_to_string(&args.path)
+        .map_err(|error| format!("Cannot read `{}`: {error}", args.path))?;
+
+    let (width, height, cells) = if args.path.ends_with(".cells") {
+        plaintext::parse_cells(&input)?
+    } else {
+        rle::parse_rle(&input)?
+    };
+
+    // A pattern grows by at most one cell per generation, so a margin of `steps` cells on each side
+    // keeps it away from the edges and the board behaves as an unbounded plane
+    let margin = args.steps;
+    let too_large = || format!("The pattern cannot be run for {} generations", args.steps);
+
+    let board_width = margin
+        .checked_mul(2)
+        .and_then(|margins| margins.checked_add(width))
+        .ok_or_else(too_large)?;
+    let board_height = margin
+        .checked_mul(2)
+        .and_then(|margins| margins.checked_add(height))
+        .ok_or_else(too_large)?;
+
+    let rule = Rule::default();
+
+    let mut alive: HashSet<(u64, u64)> = cells
+        .iter()
+        .map(|&(x, y)| (x + margin, y + margin))
+        .collect();
+
+    for _ in 0..args.steps {
+        alive = simulation::step(&alive, board_width, board_height, BoundaryMode::Dead, &rule);
+    }
+
+    let min_x = alive.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = alive.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let max_x = alive.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+    let max_y = alive.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+
+    let mut cropped: Vec<(u64, u64)> = alive.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+
+    cropped.sort_unstable();
+
+    let (crop_width, crop_height) = (max_x - min_x, max_y - min_y);
+
+    Ok(match args.format {
+        Format::Rle => format!(
+            "{}\n",
+            rle::write_rle(crop_width, crop_height, &cropped, &rule).trim_end()
+        ),
+        Format::Cells => plaintext::write_cells(crop_width, crop_height, &cropped),
+        Format::Count => format!("{}\n", cropped.len()),
+    })
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&args) {
+        Ok(output) => {
+            print!("{output}");
+
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a pattern to a file of the temporary directory, named after the test so that the tests do not collide
+    fn pattern_file(name: &str, contents: &str) -> String {
+        let path = env::temp_dir().join(format!("gol-{}-{name}", std::process::id()));
+
+        fs::write(&path, contents).unwrap();
+
+        path.to_string_lossy().into_owned()
+    }
+
+    /// Parse the arguments given as string slices
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn count_a_glider() {
+        let path = pattern_file("count.rle", "x = 3, y = 3\nbo$2bo$3o!");
+
+        let output = run(&args(&[&path, "--steps", "4", "--format", "count"]).unwrap());
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(output, Ok("5\n".to_string()));
+    }
+
+    #[test]
+    fn print_a_glider() {
+        let path = pattern_file("print.cells", "!Name: Glider\n.O\n..O\nOOO\n");
+
+        let cells = run(&args(&[&path, "--steps", "4", "--format", "cells"]).unwrap());
+        let rle = run(&args(&[&path, "--steps", "4"]).unwrap());
+
+        fs::remove_file(&path).unwrap();
+
+        // After 4 generations the glider has its initial shape again, cropped to its bounding box
+        assert_eq!(cells, Ok(".O.\n..O\nOOO\n".to_string()));
+        assert_eq!(
+            rle,
+            Ok("x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n".to_string())
+        );
+    }
+
+    #[test]
+    fn reject_invalid_arguments() {
+        let invalid = [
+            (
+                vec!["glider.rle", "--format", "svg"],
+                "Unknown format `svg`",
+            ),
+            (
+                vec!["glider.rle", "--format"],
+                "Missing value for `--format`",
+            ),
+            (
+                vec!["glider.rle", "--steps", "-1"],
+                "Invalid number of steps `-1`",
+            ),
+            (vec!["glider.rle", "--steps"], "Missing value for `--steps`"),
+            (vec!["glider.rle", "--speed"], "Unknown option `--speed`"),
+            (
+                vec!["glider.rle", "glider.cells"],
+                "Unexpected argument `glider.cells`",
+            ),
+            (vec!["--steps", "4"], "Missing the pattern file"),
+        ];
+
+        for (arguments, error) in invalid {
+            assert_eq!(args(&arguments).unwrap_err(), error, "{arguments:?}");
+        }
+    }
+
+    #[test]
+    fn report_a_missing_file() {
+        let output = run(&args(&["/nonexistent/glider.rle"]).unwrap());
+
+        assert!(output
+            .unwrap_err()
+            .starts_with("Cannot read `/nonexistent/glider.rle`"));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::config::BoundaryMode;
+use crate::rule::Rule;
+use crate::simulation::{neighbor_position, neighbor_positions};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+
+/// The number of cells stored in a word of the grid
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A struct representing the states of the cells of a board packed in a bitset.
+/// The cell at `(x, y)` is stored at the index `y * width + x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    /// The width of the grid
+    width: u64,
+
+    /// The height of the grid
+    height: u64,
+
+    /// The states of the cells, one bit per cell
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Create a new grid with the given width and height, with all cells dead.
+    /// The number of cells must fit in memory, e.g. the size of a board checked against `MAX_CELLS`.
+    pub fn new(width: u64, height: u64) -> Self {
+        // The cast does not truncate as long as the grid is not larger than a board, which has at most `MAX_CELLS` cells
+        Self {
+            width,
+            height,
+            words: vec![0; ((width * height) as usize).div_ceil(WORD_BITS)],
+        }
+    }
+
+    /// Get the number of cells of the grid
+    fn cell_count(&self) -> usize {
+        // Fits in a `usize`, see `new`
+        (self.width * self.height) as usize
+    }
+
+    /// Get the index of the cell at the given position
+    pub fn index(&self, x: u64, y: u64) -> usize {
+        // Below the number of cells for a position on the grid, so it fits in a `usize`, see `new`
+        (y * self.width + x) as usize
+    }
+
+    /// Get the position of the cell at the given index
+    pub fn position(&self, index: usize) -> (u64, u64) {
+        let index = index as u64;
+
+        (index % self.width, index / self.width)
+    }
+
+    /// Whether the cell at the given index is alive or not
+    fn is_alive_at(&self, index: usize) -> bool {
+        self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+    }
+
+    /// Whether the cell at the given position is alive or not.
+    /// The positions outside the grid are dead.
+    pub fn get(&self, x: u64, y: u64) -> bool {
+        x < self.width && y < self.height && self.is_alive_at(self.index(x, y))
+    }
+
+    /// Set the state of the cell at the given position.
+    /// The positions outside the grid are ignored.
+    pub fn set(&mut self, x: u64, y: u64, alive: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let index = self.index(x, y);
+        let mask = 1 << (index % WORD_BITS);
+
+        if alive {
+            self.words[index / WORD_BITS] |= mask;
+        } else {
+            self.words[index / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// Whether several offsets can lead to the same neighbor which only counts once,
+    /// which happens on toroidal boards not larger than the neighborhood
+    fn may_repeat_neighbors(&self, boundary: BoundaryMode, offsets: &[(i64, i64)]) -> bool {
+        let reach = offsets
+            .iter()
+            .map(|&(dx, dy)| dx.unsigned_abs().max(dy.unsigned_abs()))
+            .max()
+            .unwrap_or(0);
+
+        boundary == BoundaryMode::Toroidal && (self.width <= 2 * reach || self.height <= 2 * reach)
+    }
+
+    /// Get the number of alive neighbors of the cell at the given position,
+    /// with the neighbors at the given offsets
+    pub fn count_alive_neighbors(
+        &self,
+        x: u64,
+        y: u64,
+        boundary: BoundaryMode,
+        offsets: &[(i64, i64)],
+    ) -> usize {
+        let may_repeat = self.may_repeat_neighbors(boundary, offsets);
+
+        self.count_neighbors(x, y, boundary, offsets, may_repeat)
+    }
+
+    /// Get the number of alive neighbors of the cell at the given position.
+    /// If `may_repeat`, the neighbors reached by several offsets only count once.
+    fn count_neighbors(
+        &self,
+        x: u64,
+        y: u64,
+        boundary: BoundaryMode,
+        offsets: &[(i64, i64)],
+        may_repeat: bool,
+    ) -> usize {
+        if !may_repeat {
+            return offsets
+                .iter()
+                .filter_map(|&(dx, dy)| {
+                    neighbor_position(x, y, dx, dy, self.width, self.height, boundary)
+                })
+                .filter(|&(nx, ny)| self.is_alive_at(self.index(nx, ny)))
+                .count();
+        }
+
+        neighbor_positions(x, y, offsets, self.width, self.height, boundary)
+            .into_iter()
+            .filter(|&(nx, ny)| self.is_alive_at(self.index(nx, ny)))
+            .count()
+    }
+
+    /// Compute the next generation of the grid according to the given rule, sweeping every cell,
+    /// with the neighbors of a cell at the given offsets
+    pub fn step(&self, rule: &Rule, boundary: BoundaryMode, offsets: &[(i64, i64)]) -> BitGrid {
+        let mut next = BitGrid::new(self.width, self.height);
+        let cell_count = self.cell_count();
+        let may_repeat = self.may_repeat_neighbors(boundary, offsets);
+
+        next.words
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(word_index, word)| {
+                let start = word_index * WORD_BITS;
+                let end = (start + WORD_BITS).min(cell_count);
+
+                for index in start..end {
+                    let (x, y) = self.position(index);
+
+                    let alive = self.is_alive_at(index);
+                    let alive_neighbors = self.count_neighbors(x, y, boundary, offsets, may_repeat);
+
+                    if rule.next_state(alive, alive_neighbors) {
+                        *word |= 1 << (index - start);
+                    }
+                }
+            });
+
+        next
+    }
+
+    /// Get the indices of the cells whose state differs between the two grids of the same size
+    pub fn changed_indices<'a>(&'a self, other: &'a BitGrid) -> impl Iterator<Item = usize> + 'a {
+        self.words.iter().zip(&other.words).enumerate().flat_map(
+            |(word_index, (word, other_word))| {
+                let mut changes = word ^ other_word;
+
+                std::iter::from_fn(move || {
+                    if changes == 0 {
+                        return None;
+                    }
+
+                    let bit = changes.trailing_zeros() as usize;
+
+                    // Clear the lowest changed bit
+                    changes &= changes - 1;
+
+                    Some(word_index * WORD_BITS + bit)
+                })
+            },
+        )
+    }
+
+    /// Get the states of the cells packed in bytes, one bit per cell in the order of their indices.
+    /// The cell at index `i` is the bit `i % 8` of the byte `i / 8`, the lowest bit being the bit 0.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+
+        bytes.truncate(self.cell_count().div_ceil(8));
+
+        bytes
+    }
+}
@@ -1,26 +1,185 @@
+use crate::bitgrid::BitGrid;
 use crate::cell::Cell;
+use crate::config::{
+    self, Backend, BoundaryMode, EngineConfig, NeighborMask, DEFAULT_ACTIVITY_WINDOW,
+    DEFAULT_NEIGHBOR_RADIUS, DEFAULT_POPULATION_HISTORY_LENGTH, DEFAULT_SNAPSHOT_INTERVAL,
+};
+use crate::diff::GenerationDiff;
+use crate::error::{lock, BoardError};
+use crate::hashlife::HashlifeBoard;
+use crate::life106;
+use crate::parallel;
+use crate::patterns::Pattern;
+use crate::plaintext;
+use crate::recorder::Recorder;
+use crate::rle;
+use crate::rule::Rule;
+use crate::save::{BoardSnapshot, SavedBoard};
+use crate::simulation;
+use crate::stats::BoardStats;
 use dashmap::DashMap;
-use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use std::sync::{Arc, Mutex};
+use image::{GrayImage, ImageFormat, Luma};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+use rayon::slice::ParallelSliceMut;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+/// The largest number of cells of a board, 2^24, e.g. 4096x4096.
+/// Each cell takes about 360 bytes: the `Arc<Cell>` with its list of neighbors, its entries in the maps
+/// by id and by position and its slot in the grid, so the largest board takes about 6 GiB.
+pub const MAX_CELLS: u64 = 1 << 24;
+
+/// The number of past generations kept to step the board backward
+const HISTORY_LENGTH: usize = 50;
+
+/// The largest number of pixels of an image rendered by `render_png`, 16384x16384,
+/// so that the grayscale image, one byte per pixel, takes at most 256 MiB
+pub const MAX_IMAGE_PIXELS: u64 = 1 << 28;
+
+/// The longest period looked for by `run_until_stable`, enough for the common oscillators of a soup
+const MAX_SETTLED_PERIOD: usize = 15;
+
+/// The largest number of frames returned by `advance_sampled`, so that a long burst does not exhaust the memory
+pub const MAX_SAMPLED_FRAMES: usize = 1000;
+
+/// The density of the random soups tried by `soup_search`
+pub const SOUP_DENSITY: f64 = 0.5;
+
+/// The number of generations after which a soup of `soup_search` is given up if it did not settle
+const SOUP_MAX_GENERATIONS: usize = 2000;
+
+/// The number of generations of a soup computed between two checks of the cancellation of `soup_search`
+const SOUP_CANCEL_CHECK_INTERVAL: usize = 50;
+
+/// The offset basis and the prime of the 64-bit FNV-1a hash, used by `state_hash`
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// A rectangle of positions on the board, as the ranges of its columns and rows
+type Region = (Range<u64>, Range<u64>);
+
 /// A struct representing a board
 #[derive(Debug)]
 pub struct Board {
     /// The width of the board
-    width: Mutex<usize>,
+    width: Mutex<u64>,
 
     /// The height of the board
-    height: Mutex<usize>,
+    height: Mutex<u64>,
 
     /// The generation of the board
     generation: Mutex<usize>,
 
+    /// The birth/survival rule applied to compute the next generation
+    rule: Mutex<Rule>,
+
+    /// What lies beyond the edges of the board
+    boundary: Mutex<BoundaryMode>,
+
+    /// How the next generation of the board is computed
+    backend: Mutex<Backend>,
+
+    /// The radius of the neighborhood of a cell, 1 for the eight surrounding cells
+    neighbor_radius: Mutex<usize>,
+
+    /// Which of the cells within the neighborhood radius are the neighbors of a cell
+    neighbor_mask: Mutex<NeighborMask>,
+
+    /// Held while the cells or their neighbor links are rebuilt, while a generation is computed from them,
+    /// and while the states of the cells are changed, see `lock_edit`.
+    /// A generation never sees a half-built board, and a cell never changes state while its neighbors are linked,
+    /// which would count it twice or not at all in their alive neighbor counts.
+    /// Taken before any other lock of the board but `step_lock`.
+    topology: Mutex<()>,
+
+    /// The number of edits of the cells so far, bumped by every edit of the cells or of the rules they follow,
+    /// see `lock_edit`. A diff stamped with an older version was computed before an edit and cannot be committed.
+    version: AtomicUsize,
+
     /// The list of cells on the board
     cells: DashMap<Uuid, Arc<Cell>>,
 
+    /// The cells of the board indexed by position, the cell at `(x, y)` being at `y * width + x`.
+    /// Rebuilt with the cells under the topology lock, see `index_cells`.
+    grid: Mutex<Vec<Option<Arc<Cell>>>>,
+
+    /// The grid indices of the cells changed by the last generation, with the version of the cells right after it.
+    /// As long as the version is the same, only these cells and their neighbors can change at the next generation.
+    changed_indices: Mutex<Option<(usize, Vec<usize>)>>,
+
     /// A map of cell positions to cell ids
-    position_to_id: DashMap<(usize, usize), Uuid>,
+    position_to_id: DashMap<(u64, u64), Uuid>,
+
+    /// The positions of the cells that flipped during the last generations, the most recent first
+    recent_changes: Mutex<VecDeque<Vec<(u64, u64)>>>,
+
+    /// The number of past generations kept in `recent_changes`
+    activity_window: Mutex<usize>,
+
+    /// The states of the cells before each of the last generations, the most recent first.
+    /// Only the cells updated by a generation are recorded.
+    history: Mutex<VecDeque<Vec<(Uuid, bool)>>>,
+
+    /// The positions of the alive cells at some past generations, keyed by generation
+    snapshots: Mutex<BTreeMap<usize, HashSet<(u64, u64)>>>,
+
+    /// The number of generations between two snapshots
+    snapshot_interval: Mutex<usize>,
+
+    /// Whether the ids of the new cells are derived from their position instead of being random
+    deterministic_ids: Mutex<bool>,
+
+    /// The generation and the population of the board after each of the last generations, the oldest first
+    population_history: Mutex<VecDeque<(usize, usize)>>,
+
+    /// The number of generations kept in `population_history`
+    population_history_length: Mutex<usize>,
+
+    /// Whether the board is running on its own, cleared to stop the auto-run thread
+    auto_running: AtomicBool,
+
+    /// The thread computing the generations while the board is running on its own
+    auto_thread: Mutex<Option<JoinHandle<()>>>,
+
+    /// Set to stop the running soup search, see `cancel_soup_search`
+    soup_search_cancelled: AtomicBool,
+
+    /// Whether the user is editing the cells, the steps are skipped meanwhile, see `begin_edit`
+    editing: AtomicBool,
+
+    /// Held during a whole step, so that `begin_edit` can wait for the step in progress
+    step_lock: Mutex<()>,
+
+    /// The recording of the generations, see `start_recording`
+    recorder: Mutex<Recorder>,
+
+    /// The number of alive cells, kept up to date by the cells themselves.
+    /// Replaced when the cells are cleared, so that the cells of a previous board cannot change it.
+    population: Mutex<Arc<AtomicUsize>>,
+}
+
+/// Check that a board of the given size has at most `MAX_CELLS` cells.
+/// A board with a width or a height of 0 is valid, it has no cell.
+fn check_dimensions(width: u64, height: u64) -> Result<(), BoardError> {
+    match width.checked_mul(height) {
+        Some(cells) if cells <= MAX_CELLS => Ok(()),
+        _ => Err(BoardError::InvalidDimensions { width, height }),
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Board {
@@ -30,37 +189,232 @@ impl Board {
             width: Mutex::new(0),
             height: Mutex::new(0),
             generation: Mutex::new(0),
+            rule: Mutex::new(Rule::default()),
+            boundary: Mutex::new(BoundaryMode::default()),
+            backend: Mutex::new(Backend::default()),
+            neighbor_radius: Mutex::new(DEFAULT_NEIGHBOR_RADIUS),
+            neighbor_mask: Mutex::new(NeighborMask::default()),
+            topology: Mutex::new(()),
+            version: AtomicUsize::new(0),
             cells: DashMap::new(),
+            grid: Mutex::new(Vec::new()),
+            changed_indices: Mutex::new(None),
             position_to_id: DashMap::new(),
+            recent_changes: Mutex::new(VecDeque::new()),
+            activity_window: Mutex::new(DEFAULT_ACTIVITY_WINDOW),
+            history: Mutex::new(VecDeque::new()),
+            snapshots: Mutex::new(BTreeMap::new()),
+            snapshot_interval: Mutex::new(DEFAULT_SNAPSHOT_INTERVAL),
+            deterministic_ids: Mutex::new(false),
+            population_history: Mutex::new(VecDeque::new()),
+            population_history_length: Mutex::new(DEFAULT_POPULATION_HISTORY_LENGTH),
+            auto_running: AtomicBool::new(false),
+            auto_thread: Mutex::new(None),
+            soup_search_cancelled: AtomicBool::new(false),
+            editing: AtomicBool::new(false),
+            step_lock: Mutex::new(()),
+            recorder: Mutex::new(Recorder::default()),
+            population: Mutex::new(Arc::new(AtomicUsize::new(0))),
         }
     }
 
     /// Set the size of the board
-    fn set_size(&self, width: usize, height: usize) {
-        let mut board_width = self.width.lock().unwrap();
-        let mut board_height = self.height.lock().unwrap();
+    fn set_size(&self, width: u64, height: u64) {
+        let mut board_width = lock(&self.width);
+        let mut board_height = lock(&self.height);
         *board_width = width;
         *board_height = height;
     }
 
-    /// Increment the generation of the board by 1
-    fn increment_generation(&self) {
-        let mut generation = self.generation.lock().unwrap();
-        *generation += 1;
+    /// Get the width and the height of the board, `(0, 0)` before a board is created
+    pub fn dimensions(&self) -> (u64, u64) {
+        (*lock(&self.width), *lock(&self.height))
+    }
+
+    /// Get the birth/survival rule of the board
+    pub fn get_rule(&self) -> Rule {
+        lock(&self.rule).clone()
+    }
+
+    /// Set the birth/survival rule of the board, e.g. `[3, 6]` and `[2, 3]` for HighLife (B36/S23)
+    /// The neighbor counts of the rule must be reachable with the neighborhood of the board.
+    pub fn set_rule(&self, birth: Vec<u8>, survival: Vec<u8>) -> Result<(), BoardError> {
+        let rule = Rule::new(birth, survival);
+
+        let _edit = self.lock_edit();
+
+        let mut board_rule = lock(&self.rule);
+        let radius = *lock(&self.neighbor_radius);
+        let neighbor_count = lock(&self.neighbor_mask).offsets(radius).len();
+
+        rule.validate(neighbor_count)
+            .map_err(BoardError::InvalidConfig)?;
+
+        *board_rule = rule;
+
+        Ok(())
+    }
+
+    /// Get the radius of the neighborhood of a cell
+    pub fn get_radius(&self) -> usize {
+        *lock(&self.neighbor_radius)
+    }
+
+    /// Set the radius of the neighborhood of a cell, e.g. 2 for the 24 cells within `[-2, 2]²`
+    /// as in Larger than Life, and link the cells to their new neighbors.
+    /// Returns an error if the radius is not supported, if an offset of the neighbor mask is outside of it
+    /// or if the rule uses more neighbors than the radius gives.
+    pub fn set_radius(&self, radius: usize) -> Result<(), BoardError> {
+        config::validate_radius(radius).map_err(BoardError::InvalidConfig)?;
+
+        let _edit = self.lock_edit();
+
+        let changed = {
+            let rule = lock(&self.rule);
+            let mut neighbor_radius = lock(&self.neighbor_radius);
+            let neighbor_mask = lock(&self.neighbor_mask);
+
+            neighbor_mask
+                .validate(radius)
+                .map_err(BoardError::InvalidConfig)?;
+            rule.validate(neighbor_mask.offsets(radius).len())
+                .map_err(BoardError::InvalidConfig)?;
+
+            std::mem::replace(&mut *neighbor_radius, radius) != radius
+        };
+
+        if changed {
+            self.recompute_neighbors(None);
+        }
+
+        Ok(())
+    }
+
+    /// Get which of the cells within the neighborhood radius are the neighbors of a cell
+    pub fn get_neighbor_mask(&self) -> NeighborMask {
+        lock(&self.neighbor_mask).clone()
+    }
+
+    /// Set which of the cells within the neighborhood radius are the neighbors of a cell,
+    /// e.g. `NeighborMask::VonNeumann` for the four orthogonal cells, and link the cells to their new neighbors.
+    /// Returns an error if an offset of the mask is outside of the radius or if the rule uses more neighbors than the mask gives.
+    pub fn set_neighbor_mask(&self, mask: NeighborMask) -> Result<(), BoardError> {
+        let _edit = self.lock_edit();
+
+        let changed = {
+            let rule = lock(&self.rule);
+            let radius = *lock(&self.neighbor_radius);
+            let mut neighbor_mask = lock(&self.neighbor_mask);
+
+            mask.validate(radius).map_err(BoardError::InvalidConfig)?;
+            rule.validate(mask.offsets(radius).len())
+                .map_err(BoardError::InvalidConfig)?;
+
+            let changed = *neighbor_mask != mask;
+            *neighbor_mask = mask;
+
+            changed
+        };
+
+        if changed {
+            self.recompute_neighbors(None);
+        }
+
+        Ok(())
+    }
+
+    /// Get the offsets of the neighbors of a cell for the neighborhood radius and mask of the board
+    fn neighbor_offsets(&self) -> Vec<(i64, i64)> {
+        let radius = self.get_radius();
+
+        lock(&self.neighbor_mask).offsets(radius)
+    }
+
+    /// Get what lies beyond the edges of the board
+    pub fn get_boundary_mode(&self) -> BoundaryMode {
+        *lock(&self.boundary)
+    }
+
+    /// Set what lies beyond the edges of the board, computing the neighbors again if it changed
+    pub fn set_boundary_mode(&self, boundary: BoundaryMode) {
+        let _edit = self.lock_edit();
+
+        let changed = {
+            let mut board_boundary = lock(&self.boundary);
+
+            std::mem::replace(&mut *board_boundary, boundary) != boundary
+        };
+
+        if changed {
+            self.recompute_neighbors(None);
+        }
+    }
+
+    /// Whether the edges of the board wrap around
+    pub fn get_wrap(&self) -> bool {
+        self.get_boundary_mode() == BoundaryMode::Toroidal
+    }
+
+    /// Set whether the edges of the board wrap around, so that the left edge touches the right edge
+    /// and the top edge touches the bottom edge, or are dead walls
+    pub fn set_wrap(&self, wrap: bool) {
+        self.set_boundary_mode(if wrap {
+            BoundaryMode::Toroidal
+        } else {
+            BoundaryMode::Dead
+        });
+    }
+
+    /// Get how the next generation of the board is computed
+    pub fn get_backend(&self) -> Backend {
+        *lock(&self.backend)
+    }
+
+    /// Set how the next generation of the board is computed
+    pub fn set_backend(&self, backend: Backend) {
+        *lock(&self.backend) = backend;
+    }
+
+    /// Set whether the ids of the cells created from now on are derived from their position.
+    /// With deterministic ids, two boards built the same way, e.g. randomized with the same seed,
+    /// are identical down to the ids, which makes benchmarks reproducible.
+    /// The trade-off is that an id no longer identifies a cell uniquely across boards:
+    /// an id kept from a previous board designates the cell at the same position on the new one.
+    pub fn set_deterministic_ids(&self, deterministic_ids: bool) {
+        *lock(&self.deterministic_ids) = deterministic_ids;
     }
 
     /// Get the current generation of the board
-    /// XXX: This function is not used
-    fn _get_generation(&self) -> usize {
-        *self.generation.lock().unwrap()
+    pub fn get_generation(&self) -> usize {
+        *lock(&self.generation)
     }
 
     /// Reset the generation of the board to 0
     fn reset_generation(&self) {
-        let mut generation = self.generation.lock().unwrap();
+        let mut generation = lock(&self.generation);
         *generation = 0;
     }
 
+    /// Set the generation of the board
+    fn set_generation(&self, generation: usize) {
+        *lock(&self.generation) = generation;
+    }
+
+    /// Lock the topology of the board to change its cells or the rules they follow, and bump the version of the cells.
+    /// Every edit of the cells goes through it, so that no cell changes state while the neighbors are linked.
+    fn lock_edit(&self) -> MutexGuard<'_, ()> {
+        let topology = lock(&self.topology);
+
+        self.version.fetch_add(1, Ordering::SeqCst);
+
+        topology
+    }
+
+    /// Get the version of the cells, see `version`
+    fn get_version(&self) -> usize {
+        self.version.load(Ordering::SeqCst)
+    }
+
     /// Add a cell to the board
     fn add_cell(&self, cell: Arc<Cell>) {
         self.cells.insert(cell.id, cell);
@@ -70,7 +424,7 @@ impl Board {
     pub fn get_cell(&self, id: Uuid) -> Option<Arc<Cell>> {
         self.cells
             .get(&id)
-            .map(|cell_ref| Arc::clone(&cell_ref.value()))
+            .map(|cell_ref| Arc::clone(cell_ref.value()))
     }
 
     /// Remove a cell from the board by its id
@@ -79,14 +433,24 @@ impl Board {
         self.cells.remove(&id);
     }
 
-    /// Fill the board with cells
-    fn fill_cells(&self) {
-        let width = *self.width.lock().unwrap();
-        let height = *self.height.lock().unwrap();
+    /// Fill the given region of the board with dead cells
+    fn fill_cells(&self, (columns, rows): Region) {
+        let deterministic_ids = *lock(&self.deterministic_ids);
+        let population = Arc::clone(&lock(&self.population));
 
-        (0..width).into_par_iter().for_each(|x| {
-            (0..height).into_par_iter().for_each(|y| {
-                let new_cell = Cell::new(false, x, y);
+        columns.into_par_iter().for_each(|x| {
+            rows.clone().into_par_iter().for_each(|y| {
+                let new_cell = if deterministic_ids {
+                    Cell::with_id(
+                        Uuid::from_u128(((x as u128) << 64) | y as u128),
+                        false,
+                        x,
+                        y,
+                        &population,
+                    )
+                } else {
+                    Cell::new(false, x, y, &population)
+                };
                 let id = new_cell.id;
 
                 self.position_to_id.insert((x, y), id);
@@ -100,142 +464,3746 @@ impl Board {
     pub fn get_cells(&self) -> Vec<Arc<Cell>> {
         self.cells
             .par_iter()
-            .map(|cell| Arc::clone(&cell.value()))
+            .map(|cell| Arc::clone(cell.value()))
             .collect()
     }
 
-    /// Find a cell on the board by its position
-    /// XXX: This function is not used
-    fn _find_cell(&self, x: usize, y: usize) -> Option<Arc<Cell>> {
-        self.position_to_id
-            .get(&(x, y))
-            .and_then(|id| self.get_cell(*id))
+    /// Get the id and the position of every cell on the board
+    pub fn get_cell_positions(&self) -> Vec<(Uuid, u64, u64)> {
+        self.cells
+            .par_iter()
+            .map(|cell| (cell.id, cell.x, cell.y))
+            .collect()
     }
 
-    /// Clear all cells from the board
-    fn clear_cells(&self) {
-        self.cells.clear();
+    /// Get the id and the position of at most `limit` cells, skipping the first `offset` cells.
+    /// The cells are ordered by `(y, x)`, so that consecutive pages neither overlap nor skip a cell.
+    pub fn get_cells_page(&self, offset: usize, limit: usize) -> Vec<(Uuid, u64, u64)> {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+
+        // The board has at most `MAX_CELLS` cells, so their number fits in a `usize`
+        let end = offset.saturating_add(limit).min((width * height) as usize);
+
+        (offset.min(end)..end)
+            .into_par_iter()
+            .filter_map(|index| {
+                let index = index as u64;
+                let (x, y) = (index % width, index / width);
+
+                self.position_to_id.get(&(x, y)).map(|id| (*id, x, y))
+            })
+            .collect()
+    }
+
+    /// Get the id, the position, the state and the age of every cell on the board
+    pub fn get_cells_with_age(&self) -> Vec<(Uuid, u64, u64, bool, usize)> {
+        self.cells
+            .par_iter()
+            .map(|cell| (cell.id, cell.x, cell.y, cell.is_alive(), cell.age()))
+            .collect()
+    }
+
+    /// Count the alive cells on the board
+    pub fn count_alive(&self) -> usize {
+        self.cells
+            .par_iter()
+            .filter(|entry| entry.value().is_alive())
+            .count()
     }
 
-    /// Compute the neighbors of each cell on the board
-    fn compute_neighbors(&self) {
-        let width = *self.width.lock().unwrap();
-        let height = *self.height.lock().unwrap();
-
-        (0..width).into_par_iter().for_each(|x| {
-            (0..height).into_par_iter().for_each(|y| {
-                if let Some(cell_id) = self.position_to_id.get(&(x, y)) {
-                    if let Some(cell) = self.cells.get(&cell_id) {
-                        let neighbor_offsets = [
-                            (-1, -1),
-                            (0, -1),
-                            (1, -1),
-                            (-1, 0),
-                            (1, 0),
-                            (-1, 1),
-                            (0, 1),
-                            (1, 1),
-                        ];
-
-                        for &(dx, dy) in &neighbor_offsets {
-                            if let (Some(nx), Some(ny)) =
-                                (Cell::offset_position(x, dx), Cell::offset_position(y, dy))
-                            {
-                                if nx < width && ny < height {
-                                    if let Some(neighbor_id) = self.position_to_id.get(&(nx, ny)) {
-                                        if let Some(neighbor) = self.cells.get(&neighbor_id) {
-                                            cell.add_neighbor(nx, ny, Arc::clone(&neighbor));
-                                        }
-                                    }
-                                }
-                            }
+    /// Get the number of alive cells without going through the cells, see `population`
+    fn tracked_population(&self) -> usize {
+        lock(&self.population).load(Ordering::Relaxed)
+    }
+
+    /// Get the bounding box of the alive cells, as `(min_x, min_y, max_x, max_y)`.
+    /// Returns `None` if no cell is alive.
+    pub fn alive_bounds(&self) -> Option<(u64, u64, u64, u64)> {
+        self.cells
+            .par_iter()
+            .filter(|entry| entry.value().is_alive())
+            .map(|entry| {
+                let cell = entry.value();
+
+                (cell.x, cell.y, cell.x, cell.y)
+            })
+            .reduce_with(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+    }
+
+    /// Whether no cell of the board is alive, stopping at the first alive cell found
+    pub fn is_empty(&self) -> bool {
+        !self.cells.par_iter().any(|entry| entry.value().is_alive())
+    }
+
+    /// Get the population, the generation, the density, the bounding box and the radius of the alive cells,
+    /// in a single pass over the cells
+    pub fn stats(&self) -> BoardStats {
+        let generation = self.get_generation();
+        let total = self.cells.len();
+
+        let (population, bounding_box) = self
+            .cells
+            .par_iter()
+            .filter(|entry| entry.value().is_alive())
+            .map(|entry| {
+                let cell = entry.value();
+
+                (1, Some((cell.x, cell.y, cell.x, cell.y)))
+            })
+            .reduce(
+                || (0, None),
+                |(count_a, box_a), (count_b, box_b)| {
+                    let bounding_box = match (box_a, box_b) {
+                        (Some(a), Some(b)) => {
+                            Some((a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
                         }
-                    }
-                }
-            });
+                        (a, b) => a.or(b),
+                    };
+
+                    (count_a + count_b, bounding_box)
+                },
+            );
+
+        let density = if total == 0 {
+            0.0
+        } else {
+            population as f64 / total as f64
+        };
+
+        // The farthest alive cells from the center are on the edges of the bounding box
+        let radius = bounding_box.map_or(0.0, |(min_x, min_y, max_x, max_y)| {
+            (max_x - min_x).max(max_y - min_y) as f64 / 2.0
         });
+
+        BoardStats {
+            population,
+            generation,
+            density,
+            bounding_box,
+            radius,
+        }
     }
 
-    /// Reset the board to its initial state
-    fn reset(&self) {
-        self.reset_generation();
-        self.clear_cells();
+    /// Get the center of mass of the alive cells, e.g. for the viewport to follow a moving pattern,
+    /// in a single pass over the cells.
+    /// Returns `None` if no cell is alive.
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let (count, sum_x, sum_y) = self
+            .cells
+            .par_iter()
+            .filter(|entry| entry.value().is_alive())
+            .map(|entry| (1_u64, entry.value().x, entry.value().y))
+            .reduce(|| (0, 0, 0), |a, b| (a.0 + b.0, a.1 + b.1, a.2 + b.2));
+
+        (count > 0).then(|| (sum_x as f64 / count as f64, sum_y as f64 / count as f64))
     }
 
-    /// Create a new board with the given width and height, filling it with cells and computing the neighbors
-    pub fn create_board(&self, width: usize, height: usize) {
-        self.reset();
+    /// Get a hash of the positions of the alive cells, e.g. to compare two boards or detect a repeated state.
+    /// The hash only depends on the alive positions, not on the ids of the cells or the order they are stored in,
+    /// and is the same from one run of the program to the next.
+    pub fn state_hash(&self) -> u64 {
+        self.alive_positions()
+            .iter()
+            .flat_map(|&(x, y)| [x, y])
+            .flat_map(u64::to_le_bytes)
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
 
-        self.set_size(width, height);
+    /// Get the width and the height of the board and the states of its cells packed in bytes, row by row,
+    /// e.g. to draw the whole board at once. The cell at `(x, y)` is the bit `i % 8` of the byte `i / 8`,
+    /// with `i = y * width + x` and the lowest bit being the bit 0, see `BitGrid::to_bytes`.
+    pub fn snapshot_bitmap(&self) -> (u64, u64, Vec<u8>) {
+        let (width, height) = self.dimensions();
+
+        let mut grid = BitGrid::new(width, height);
 
-        self.fill_cells();
+        for (x, y) in self.alive_set() {
+            grid.set(x, y, true);
+        }
 
-        self.compute_neighbors();
+        (width, height, grid.to_bytes())
     }
 
-    /// Kill all cells on the board and reset the generation to 0
-    pub fn kill_board(&self) {
+    /// Get the set of the positions of the alive cells
+    fn alive_set(&self) -> HashSet<(u64, u64)> {
         self.cells
             .par_iter()
-            .for_each(|cell| cell.value().set_alive(false));
-
-        self.reset_generation();
+            .filter(|entry| entry.value().is_alive())
+            .map(|entry| (entry.value().x, entry.value().y))
+            .collect()
     }
 
-    /// Compute the next generation of the board
-    /// Returns a list of cell ids with their future state
-    pub fn compute_next_generation(&self) -> Vec<(Uuid, bool)> {
-        let relevant_cells = self.get_relevant_cells();
+    /// Get the positions of the alive cells, sorted by `(x, y)`
+    pub fn alive_positions(&self) -> Vec<(u64, u64)> {
+        let mut alive_positions: Vec<(u64, u64)> = self
+            .cells
+            .par_iter()
+            .filter(|entry| entry.value().is_alive())
+            .map(|entry| (entry.value().x, entry.value().y))
+            .collect();
+
+        alive_positions.par_sort_unstable();
+
+        alive_positions
+    }
 
-        relevant_cells
+    /// Set alive the cells at the given positions and kill all the others.
+    /// Returns the list of cell ids that changed, with their new state.
+    /// The caller must hold the edit lock, see `lock_edit`.
+    fn set_alive_set(&self, alive: &HashSet<(u64, u64)>) -> Vec<(Uuid, bool)> {
+        self.cells
             .par_iter()
-            .map(|cell| {
-                let cell = cell.value();
+            .filter_map(|entry| {
+                let cell = entry.value();
 
-                let alive = cell.compute_future_state();
+                let was_alive = cell.is_alive();
+                let alive = alive.contains(&(cell.x, cell.y));
 
-                (cell.id, alive)
+                cell.set_alive(alive);
+
+                (was_alive != alive).then_some((cell.id, alive))
             })
             .collect()
     }
 
-    /// Get the relevant cells for the next generation.
-    /// A cell is relevant if it is alive or is a neighbor of an alive cell.
-    fn get_relevant_cells(&self) -> DashMap<Uuid, Arc<Cell>> {
-        let relevant_cells = DashMap::new();
+    /// Get the state of every cell as a row-major matrix, `rows[y][x]` being the state of the cell at `(x, y)`.
+    /// The matrix has the dimensions of the board, see `set_from_matrix` for the inverse.
+    pub fn to_matrix(&self) -> Vec<Vec<bool>> {
+        let snapshot = self.snapshot();
+
+        // The board has at most `MAX_CELLS` cells, so its size and positions fit in a `usize` on every target
+        let mut rows = vec![vec![false; snapshot.width as usize]; snapshot.height as usize];
+
+        for (x, y) in snapshot.alive {
+            rows[y as usize][x as usize] = true;
+        }
+
+        rows
+    }
+
+    /// Set the state of every cell from a row-major matrix, `rows[y][x]` being the state of the cell at `(x, y)`.
+    /// Returns the list of cell ids that changed, with their new state,
+    /// or an error if the matrix does not have the dimensions of the board.
+    pub fn set_from_matrix(&self, rows: Vec<Vec<bool>>) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let (width, height) = self.dimensions();
+
+        if let Some(row) = rows.iter().find(|row| row.len() as u64 != width) {
+            return Err(BoardError::InvalidDimensions {
+                width: row.len() as u64,
+                height: rows.len() as u64,
+            });
+        }
+
+        if rows.len() as u64 != height {
+            return Err(BoardError::InvalidDimensions {
+                width,
+                height: rows.len() as u64,
+            });
+        }
+
+        let _edit = self.lock_edit();
+
+        Ok(self
+            .cells
+            .par_iter()
+            .filter_map(|entry| {
+                let cell = entry.value();
+
+                let was_alive = cell.is_alive();
+                // The matrix has the dimensions of the board, checked above, and the board at most `MAX_CELLS` cells,
+                // so the casts cannot truncate a position
+                let alive = rows[cell.y as usize][cell.x as usize];
+
+                cell.set_alive(alive);
+
+                (was_alive != alive).then_some((cell.id, alive))
+            })
+            .collect())
+    }
+
+    /// Find a cell on the board by its position
+    pub fn find_cell(&self, x: u64, y: u64) -> Option<Arc<Cell>> {
+        self.position_to_id
+            .get(&(x, y))
+            .and_then(|id| self.get_cell(*id))
+    }
+
+    /// Get the id, the position and the state of each neighbor of the cell with the given id.
+    /// Returns `None` if no cell has that id.
+    pub fn neighbors_of(&self, id: Uuid) -> Option<Vec<(Uuid, u64, u64, bool)>> {
+        let cell = self.get_cell(id)?;
+
+        Some(
+            cell.get_neighbors()
+                .iter()
+                .map(|neighbor| (neighbor.id, neighbor.x, neighbor.y, neighbor.is_alive()))
+                .collect(),
+        )
+    }
+
+    /// Count the alive neighbors of the cell at the given position, checking every neighbor.
+    /// Returns `None` if the position is outside the board.
+    pub fn alive_neighbors_at(&self, x: u64, y: u64) -> Option<usize> {
+        self.find_cell(x, y)
+            .map(|cell| cell.count_alive_neighbors())
+    }
+
+    /// Flip the state of the cell at the given position.
+    /// Returns the id of the cell and its new state, or `None` if the position is outside the board.
+    pub fn toggle_at(&self, x: u64, y: u64) -> Option<(Uuid, bool)> {
+        let cell = self.find_cell(x, y)?;
+
+        let _edit = self.lock_edit();
+
+        Some((cell.id, cell.toggle_alive()))
+    }
+
+    /// Freeze or unfreeze the cell with the given id, see `Cell::set_frozen`.
+    /// Returns `false` if no cell has this id.
+    pub fn set_frozen(&self, id: Uuid, frozen: bool) -> bool {
+        let Some(cell) = self.get_cell(id) else {
+            return false;
+        };
+
+        // Freezing a cell changes the next generation like an edit of its state
+        let _edit = self.lock_edit();
+
+        cell.set_frozen(frozen);
+
+        true
+    }
+
+    /// Invert the state of every cell on the board, the dead cells becoming alive and the alive cells dead.
+    /// Returns the list of every cell id with its new state.
+    pub fn invert(&self) -> Vec<(Uuid, bool)> {
+        let _edit = self.lock_edit();
 
         self.cells
             .par_iter()
-            // Filter out the dead cells
-            .filter(|entry| *entry.value().alive.lock().unwrap())
-            .for_each(|entry| {
+            .map(|entry| {
                 let cell = entry.value();
-                let cell_id = entry.key();
 
-                // Add the alive cell to the relevant cells
-                relevant_cells.insert(*cell_id, Arc::clone(&cell));
+                (cell.id, cell.toggle_alive())
+            })
+            .collect()
+    }
+
+    /// Clear all cells from the board
+    fn clear_cells(&self) {
+        self.cells.clear();
+        self.position_to_id.clear();
+        lock(&self.grid).clear();
 
-                // Add the neighbors of the alive cell to the relevant cells
-                let neighbors = cell.get_neighbors();
+        *lock(&self.population) = Arc::new(AtomicUsize::new(0));
+    }
 
-                for neighbor in neighbors {
-                    relevant_cells
-                        .entry(neighbor.id)
-                        .or_insert_with(|| Arc::clone(&neighbor));
-                }
-            });
+    /// Index the cells of the board by position into `grid`, after the cells were created or removed.
+    /// The caller must hold the topology lock.
+    fn index_cells(&self) {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
 
-        relevant_cells
+        // The board has at most `MAX_CELLS` cells, so the indices fit in a `usize` on every target
+        let mut grid: Vec<Option<Arc<Cell>>> = vec![None; (width * height) as usize];
+
+        for entry in self.cells.iter() {
+            let cell = entry.value();
+
+            if cell.x < width && cell.y < height {
+                grid[(cell.y * width + cell.x) as usize] = Some(Arc::clone(cell));
+            }
+        }
+
+        *lock(&self.grid) = grid;
     }
 
-    /// Update the next generation of the board with the given list of cell ids and their future state
-    pub fn update_next_generation(&self, next_gen: &Vec<(Uuid, bool)>) {
-        next_gen.par_iter().for_each(|(id, alive)| {
-            if let Some(cell) = self.get_cell(*id) {
-                cell.set_alive(*alive);
+    /// Get the cells of the board in the given region
+    fn region_cells(&self, (columns, rows): Region) -> Vec<Arc<Cell>> {
+        columns
+            .into_par_iter()
+            .flat_map_iter(|x| rows.clone().map(move |y| (x, y)))
+            .filter_map(|(x, y)| self.find_cell(x, y))
+            .collect()
+    }
+
+    /// Compute the neighbors of each cell in the given region, or of each cell on the board if `None`.
+    /// The cells of the region must have no neighbors yet, and the grid must be indexed, see `index_cells`.
+    fn compute_neighbors(&self, region: Option<Region>) {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+        let boundary = *lock(&self.boundary);
+        let offsets = self.neighbor_offsets();
+
+        let region = region.unwrap_or((0..width, 0..height));
+        let cells = self.region_cells(region);
+
+        // Looked up by index in the grid rather than through the maps, see `index_cells`
+        let grid = lock(&self.grid);
+
+        cells.par_iter().for_each(|cell| {
+            for (nx, ny) in
+                simulation::neighbor_positions(cell.x, cell.y, &offsets, width, height, boundary)
+            {
+                if let Some(Some(neighbor)) = grid.get((ny * width + nx) as usize) {
+                    cell.add_neighbor(neighbor);
+                }
             }
         });
+    }
+
+    /// Forget the neighbors of each cell in the given region, or of each cell on the board if `None`,
+    /// and compute them again, e.g. after the edge behavior changed.
+    /// The caller must hold the topology lock.
+    fn recompute_neighbors(&self, region: Option<Region>) {
+        match &region {
+            Some(region) => self
+                .region_cells(region.clone())
+                .par_iter()
+                .for_each(|cell| cell.clear_neighbors()),
+            None => self
+                .cells
+                .par_iter()
+                .for_each(|cell| cell.value().clear_neighbors()),
+        }
+
+        self.compute_neighbors(region);
+    }
+
+    /// Record the positions of the cells that flipped during the last generation
+    fn record_changes(&self, changes: Vec<(u64, u64)>) {
+        let activity_window = *lock(&self.activity_window);
+        let mut recent_changes = lock(&self.recent_changes);
+
+        recent_changes.push_front(changes);
+        recent_changes.truncate(activity_window);
+    }
+
+    /// Forget the cells that flipped during the last generations
+    fn clear_changes(&self) {
+        lock(&self.recent_changes).clear();
+    }
+
+    /// Record in the history the current states of the cells about to be updated
+    fn record_history(&self, next_gen: &[(Uuid, bool)]) {
+        let previous_states = next_gen
+            .par_iter()
+            .filter_map(|(id, _)| {
+                let cell = self.get_cell(*id)?;
+
+                let alive = cell.is_alive();
+
+                Some((*id, alive))
+            })
+            .collect();
+
+        let mut history = lock(&self.history);
+
+        history.push_front(previous_states);
+        history.truncate(HISTORY_LENGTH);
+    }
+
+    /// Restore the states of the cells recorded in the history before a generation.
+    /// Returns the list of cell ids that changed, with their restored state.
+    /// The caller must hold the edit lock, see `lock_edit`.
+    fn restore_states(&self, previous_states: &[(Uuid, bool)]) -> Vec<(Uuid, bool)> {
+        previous_states
+            .par_iter()
+            .filter_map(|&(id, alive)| {
+                let cell = self.get_cell(id)?;
+
+                let was_alive = cell.is_alive();
+
+                cell.set_alive(alive);
+
+                (was_alive != alive).then_some((id, alive))
+            })
+            .collect()
+    }
+
+    /// Forget the recorded history of the board
+    fn clear_history(&self) {
+        lock(&self.history).clear();
+    }
+
+    /// Record a snapshot of the alive cells if the given generation is a multiple of the snapshot interval
+    fn record_snapshot(&self, generation: usize) {
+        let snapshot_interval = *lock(&self.snapshot_interval);
+
+        if generation.is_multiple_of(snapshot_interval) {
+            let alive = self.alive_set();
+
+            lock(&self.snapshots).insert(generation, alive);
+        }
+    }
+
+    /// Forget the snapshots of the board
+    fn clear_snapshots(&self) {
+        lock(&self.snapshots).clear();
+    }
+
+    /// Record the current population of the board as the one of the given generation,
+    /// dropping the oldest ones beyond the history length
+    fn record_population(&self, generation: usize) {
+        let population = self.count_alive();
+        let population_history_length = *lock(&self.population_history_length);
+        let mut population_history = lock(&self.population_history);
+
+        population_history.push_back((generation, population));
+
+        while population_history.len() > population_history_length {
+            population_history.pop_front();
+        }
+    }
+
+    /// Forget the populations of the past generations
+    fn clear_population_history(&self) {
+        lock(&self.population_history).clear();
+    }
+
+    /// Get the population of the board after each of the last generations, the oldest first
+    pub fn population_history(&self) -> Vec<usize> {
+        lock(&self.population_history)
+            .iter()
+            .map(|&(_, population)| population)
+            .collect()
+    }
+
+    /// Export the population history as CSV, with a `generation,population` header and one row per generation,
+    /// the oldest first. Returns only the header if no generation was recorded.
+    pub fn export_stats_csv(&self) -> String {
+        let mut csv = String::from("generation,population\n");
+
+        for (generation, population) in lock(&self.population_history).iter() {
+            csv.push_str(&format!("{generation},{population}\n"));
+        }
+
+        csv
+    }
+
+    /// Set the number of generations whose population is kept.
+    /// The oldest populations beyond the new length are dropped.
+    /// Returns an error if the length is 0.
+    pub fn set_population_history_length(&self, length: usize) -> Result<(), BoardError> {
+        if length == 0 {
+            return Err(BoardError::InvalidConfig(
+                "The population history must keep at least 1 generation".to_string(),
+            ));
+        }
+
+        let mut population_history_length = lock(&self.population_history_length);
+        let mut population_history = lock(&self.population_history);
+
+        *population_history_length = length;
+
+        let excess = population_history.len().saturating_sub(length);
+        population_history.drain(..excess);
+
+        Ok(())
+    }
+
+    /// Set the number of generations between two snapshots of the board.
+    /// The snapshots already recorded are kept.
+    pub fn set_snapshot_interval(&self, snapshot_interval: usize) -> Result<(), BoardError> {
+        if snapshot_interval == 0 {
+            return Err(BoardError::InvalidConfig(
+                "The snapshot interval must be at least 1 generation".to_string(),
+            ));
+        }
+
+        *lock(&self.snapshot_interval) = snapshot_interval;
+
+        Ok(())
+    }
+
+    /// Start recording the generations of the board, forgetting the previous recording.
+    /// At most `max_length` generations are kept, the oldest being dropped first.
+    /// Returns an error if `max_length` is 0.
+    pub fn start_recording(&self, max_length: usize) -> Result<(), BoardError> {
+        if max_length == 0 {
+            return Err(BoardError::InvalidConfig(
+                "The recording must keep at least 1 generation".to_string(),
+            ));
+        }
+
+        // Holding the generation prevents a generation from being applied before the recording starts
+        let generation = lock(&self.generation);
+
+        let initial_states = self
+            .cells
+            .par_iter()
+            .map(|entry| (*entry.key(), entry.value().is_alive()))
+            .collect();
+
+        lock(&self.recorder).start(initial_states, *generation, max_length);
+
+        Ok(())
+    }
+
+    /// Stop recording the generations of the board, the recording is kept to be replayed.
+    /// Returns the number of recorded generations.
+    pub fn stop_recording(&self) -> usize {
+        let mut recorder = lock(&self.recorder);
+
+        recorder.stop();
+
+        recorder.len()
+    }
+
+    /// Get the list of cell ids with their new state for each recorded generation, the oldest first
+    pub fn export_recording(&self) -> Vec<Vec<(Uuid, bool)>> {
+        lock(&self.recorder).steps()
+    }
+
+    /// Set the board to its state after the given number of recorded generations, 0 being the start of the recording.
+    /// The recording is kept, so the board can be moved back and forth along it, but the history is forgotten.
+    /// Returns the list of cell ids that changed with their replayed state,
+    /// or an error if the board is still recording or if fewer generations were recorded.
+    pub fn replay_step(&self, index: usize) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let _edit = self.lock_edit();
+        let mut generation = lock(&self.generation);
+
+        let (states, replayed_generation) = {
+            let recorder = lock(&self.recorder);
+
+            if recorder.is_recording() {
+                return Err(BoardError::InvalidReplay(
+                    "Stop the recording before replaying it".to_string(),
+                ));
+            }
+
+            let states = recorder.states_at(index).ok_or_else(|| {
+                BoardError::InvalidReplay(format!(
+                    "Cannot replay step {index} of a recording of {} steps",
+                    recorder.len()
+                ))
+            })?;
+
+            (states, recorder.generation_at(index))
+        };
+
+        let changed = self.restore_states(&states);
+
+        *generation = replayed_generation;
+
+        self.clear_changes();
+        self.clear_history();
+
+        Ok(changed)
+    }
+
+    /// Forget the recording of the board, e.g. when its cells are replaced
+    fn clear_recording(&self) {
+        lock(&self.recorder).clear();
+    }
+
+    /// Restore the board to a past generation from its snapshot.
+    /// The snapshots of the later generations and the history are forgotten.
+    /// Returns the list of cell ids that changed with their restored state,
+    /// or an error if no snapshot was recorded for that generation.
+    pub fn restore_generation(&self, target: usize) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let _edit = self.lock_edit();
+        let mut generation = lock(&self.generation);
+
+        let alive = {
+            let mut snapshots = lock(&self.snapshots);
+
+            let alive = snapshots
+                .get(&target)
+                .cloned()
+                .ok_or(BoardError::MissingSnapshot { generation: target })?;
+
+            snapshots.split_off(&(target + 1));
+
+            alive
+        };
+
+        let changed = self.set_alive_set(&alive);
+
+        *generation = target;
+
+        self.clear_changes();
+        self.clear_history();
+
+        Ok(changed)
+    }
+
+    /// Reset the board to its initial state
+    fn reset(&self) {
+        self.reset_generation();
+        self.clear_changes();
+        self.clear_history();
+        self.clear_snapshots();
+        self.clear_population_history();
+        self.clear_recording();
+        self.clear_cells();
+    }
+
+    /// Create a new board with the given width and height, filling it with cells and computing the neighbors
+    /// Returns an error if the number of cells of the board would exceed `MAX_CELLS`.
+    pub fn create_board(&self, width: u64, height: u64) -> Result<(), BoardError> {
+        self.build_board(width, height, &[])
+    }
+
+    /// Create a new board with the given width and height and the cells at the given positions alive.
+    /// The positions outside the board are skipped.
+    /// Returns the id and the position of every cell on the board,
+    /// or an error if the number of cells of the board would exceed `MAX_CELLS`.
+    pub fn create_with_cells(
+        &self,
+        width: u64,
+        height: u64,
+        alive: &[(u64, u64)],
+    ) -> Result<Vec<(Uuid, u64, u64)>, BoardError> {
+        self.build_board(width, height, alive)?;
+
+        Ok(self.get_cell_positions())
+    }
+
+    /// Fill a new board of the given size with cells, the ones at the given positions alive, see `rebuild_cells`.
+    /// Returns an error if the number of cells of the board would exceed `MAX_CELLS`.
+    fn build_board(&self, width: u64, height: u64, alive: &[(u64, u64)]) -> Result<(), BoardError> {
+        check_dimensions(width, height)?;
+
+        let _edit = self.lock_edit();
+
+        self.rebuild_cells(width, height, alive);
+
+        Ok(())
+    }
+
+    /// Replace the cells with a new board of the given size, the cells at the given positions alive.
+    /// The cells are set alive before the neighbors are computed, so that the neighbors are not updated one by one.
+    /// The caller must hold the edit lock, see `lock_edit`, and check the dimensions, see `check_dimensions`.
+    fn rebuild_cells(&self, width: u64, height: u64, alive: &[(u64, u64)]) {
+        self.reset();
+
+        self.set_size(width, height);
+
+        self.fill_cells((0..width, 0..height));
+        self.index_cells();
+
+        alive.par_iter().for_each(|&(x, y)| {
+            if let Some(cell) = self.find_cell(x, y) {
+                cell.set_alive(true);
+            }
+        });
+
+        self.compute_neighbors(None);
+    }
+
+    /// Resize the board, keeping the cells that still fit in the new size and the generation.
+    /// Only the neighbors of the cells near the edges that moved are computed again.
+    /// The history of the board is lost.
+    /// Returns the id and the position of every cell on the resized board,
+    /// or an error if the number of cells of the board would exceed `MAX_CELLS`.
+    pub fn resize(
+        &self,
+        new_width: u64,
+        new_height: u64,
+    ) -> Result<Vec<(Uuid, u64, u64)>, BoardError> {
+        check_dimensions(new_width, new_height)?;
+
+        let _edit = self.lock_edit();
+
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+        let wrap = self.get_wrap();
+        let radius = self.get_radius() as u64;
+
+        // Kill the cells outside of the new size before removing them, so that their neighbors forget them
+        let removed: Vec<Arc<Cell>> = self
+            .cells
+            .par_iter()
+            .filter(|entry| entry.value().x >= new_width || entry.value().y >= new_height)
+            .map(|entry| Arc::clone(entry.value()))
+            .collect();
+
+        removed.par_iter().for_each(|cell| cell.set_alive(false));
+
+        removed.par_iter().for_each(|cell| {
+            self.cells.remove(&cell.id);
+            self.position_to_id.remove(&(cell.x, cell.y));
+        });
+
+        self.set_size(new_width, new_height);
+
+        let kept_width = width.min(new_width);
+        let kept_height = height.min(new_height);
+
+        self.fill_cells((kept_width..new_width, 0..new_height));
+        self.fill_cells((0..kept_width, kept_height..new_height));
+        self.index_cells();
+
+        // The cells whose neighborhood crosses an edge that moved, including the opposite edge when wrapping
+        let mut dirty_regions = Vec::new();
+
+        if new_width != width {
+            dirty_regions.push((kept_width.saturating_sub(radius)..new_width, 0..new_height));
+
+            if wrap {
+                dirty_regions.push((0..radius.min(new_width), 0..new_height));
+            }
+        }
+
+        if new_height != height {
+            dirty_regions.push((0..new_width, kept_height.saturating_sub(radius)..new_height));
+
+            if wrap {
+                dirty_regions.push((0..new_width, 0..radius.min(new_height)));
+            }
+        }
+
+        for region in dirty_regions {
+            self.recompute_neighbors(Some(region));
+        }
+
+        self.clear_changes();
+        self.clear_history();
+        self.clear_snapshots();
+
+        Ok(self.get_cell_positions())
+    }
+
+    /// Trim the board to the bounding box of the alive cells plus `margin` dead cells on each side,
+    /// moving the alive cells accordingly, e.g. to free the memory of a board whose pattern collapsed.
+    /// An empty board becomes 0x0, whatever the margin.
+    /// The cells are created again with new ids, and only the generation and the settings of the board are kept.
+    /// Returns the new width and height of the board,
+    /// or an error if the number of cells of the board would exceed `MAX_CELLS`.
+    pub fn shrink_to_fit(&self, margin: u64) -> Result<(u64, u64), BoardError> {
+        // Held from the snapshot to the new cells, so that no generation or edit is lost in between
+        let _step = lock(&self.step_lock);
+        let _edit = self.lock_edit();
+
+        let snapshot = self.snapshot();
+
+        let min_x = snapshot.alive.iter().map(|&(x, _)| x).min();
+        let min_y = snapshot.alive.iter().map(|&(_, y)| y).min();
+        let max_x = snapshot.alive.iter().map(|&(x, _)| x).max();
+        let max_y = snapshot.alive.iter().map(|&(_, y)| y).max();
+
+        let (width, height, alive) = match (min_x, min_y, max_x, max_y) {
+            (Some(min_x), Some(min_y), Some(max_x), Some(max_y)) => {
+                let size =
+                    |min: u64, max: u64| (max - min + 1).saturating_add(margin.saturating_mul(2));
+
+                let (width, height) = (size(min_x, max_x), size(min_y, max_y));
+
+                // Checked before moving the cells, so that a huge margin cannot overflow their positions
+                check_dimensions(width, height)?;
+
+                let alive: Vec<(u64, u64)> = snapshot
+                    .alive
+                    .iter()
+                    .map(|&(x, y)| (x - min_x + margin, y - min_y + margin))
+                    .collect();
+
+                (width, height, alive)
+            }
+            _ => (0, 0, Vec::new()),
+        };
+
+        self.rebuild_cells(width, height, &alive);
+
+        self.set_generation(snapshot.generation);
+
+        Ok((width, height))
+    }
+
+    /// Kill all cells on the board and reset the generation to 0.
+    /// The history, the snapshots, the population history and the recording are forgotten too,
+    /// so that nothing is carried over to the next experiment. The ages are reset as the cells die.
+    pub fn kill_board(&self) {
+        let _edit = self.lock_edit();
+
+        self.cells
+            .par_iter()
+            .for_each(|cell| cell.value().set_alive(false));
+
+        self.reset_generation();
+        self.clear_changes();
+        self.clear_history();
+        self.clear_snapshots();
+        self.clear_population_history();
+        self.clear_recording();
+    }
+
+    /// Empty the board completely, removing every cell and setting its size to 0x0, e.g. to free memory.
+    /// The board stops running on its own, and the settings of the engine are kept.
+    pub fn dispose(&self) {
+        self.stop_auto();
+
+        let _edit = self.lock_edit();
+
+        self.reset();
+
+        self.set_size(0, 0);
+    }
+
+    /// Compute the next generation of the board
+    /// Returns a list of the ids of the cells whose state changes, with their future state.
+    /// The board and its generation counter are left unchanged, `update_next_generation` commits the result.
+    pub fn compute_next_generation(&self) -> Vec<(Uuid, bool)> {
+        let _topology = lock(&self.topology);
+
+        parallel::run(self.cells.len(), || match self.get_backend() {
+            // Every cell of an empty board has no alive neighbors, so whatever the backend nothing is born
+            // unless the rule has B0, and the cells are not even gone through
+            _ if self.tracked_population() == 0 && !self.get_rule().birth.contains(&0) => {
+                Vec::new()
+            }
+            Backend::Cells => self.compute_next_generation_cells(),
+            Backend::Bitset => self.compute_next_generation_bitset(),
+            Backend::Simulation => self.compute_next_generation_simulation(),
+        })
+    }
+
+    /// Compute the next generation by counting the neighbors of the relevant cells through their links.
+    /// Under a rule with B0, every cell is relevant, as the dead cells without alive neighbors are born.
+    fn compute_next_generation_cells(&self) -> Vec<(Uuid, bool)> {
+        let rule = self.get_rule();
+        let relevant_indices = self.relevant_indices(rule.next_state(false, 0));
+
+        // The cells are borrowed from the grid, without cloning their `Arc`
+        let grid = lock(&self.grid);
+
+        relevant_indices
+            .par_iter()
+            .filter_map(|&index| {
+                let cell = grid[index].as_ref()?;
+
+                let alive = cell.is_alive();
+                let future_alive = cell.compute_future_state(&rule);
+
+                // Skip the cells that keep their state, there is nothing to update for them
+                (future_alive != alive).then_some((cell.id, future_alive))
+            })
+            .collect()
+    }
+
+    /// Gather the grid indices of the relevant cells, see `is_relevant`, or of every cell if `born_alone`.
+    /// Right after a generation, only the cells it changed and their neighbors are gathered,
+    /// the other cells keeping their state again, otherwise every cell of the grid is checked.
+    fn relevant_indices(&self, born_alone: bool) -> Vec<usize> {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+        let boundary = self.get_boundary_mode();
+        let offsets = self.neighbor_offsets();
+
+        let grid = lock(&self.grid);
+        let changed_indices = lock(&self.changed_indices);
+
+        match &*changed_indices {
+            Some((version, changed)) if *version == self.get_version() && !born_alone => {
+                let mut indices: Vec<usize> = changed
+                    .par_iter()
+                    .flat_map_iter(|&index| {
+                        // Below the number of cells, so the position fits in a `u64` and back
+                        let (x, y) = (index as u64 % width, index as u64 / width);
+
+                        simulation::neighbor_positions(x, y, &offsets, width, height, boundary)
+                            .into_iter()
+                            .map(move |(nx, ny)| (ny * width + nx) as usize)
+                            .chain(std::iter::once(index))
+                    })
+                    .collect();
+
+                indices.par_sort_unstable();
+                indices.dedup();
+
+                indices
+            }
+            _ => grid
+                .par_iter()
+                .enumerate()
+                .filter(|(_, cell)| {
+                    cell.as_ref()
+                        .is_some_and(|cell| born_alone || Self::is_relevant(cell))
+                })
+                .map(|(index, _)| index)
+                .collect(),
+        }
+    }
+
+    /// Compute the next generation by packing the states of the cells in a bitset and sweeping every cell
+    fn compute_next_generation_bitset(&self) -> Vec<(Uuid, bool)> {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+        let boundary = self.get_boundary_mode();
+        let rule = self.get_rule();
+
+        let grid = lock(&self.grid);
+        let mut current = BitGrid::new(width, height);
+
+        for cell in grid.iter().flatten().filter(|cell| cell.is_alive()) {
+            current.set(cell.x, cell.y, true);
+        }
+
+        let next = current.step(&rule, boundary, &self.neighbor_offsets());
+
+        // Only map the changed cells back to their ids
+        current
+            .changed_indices(&next)
+            .filter_map(|index| {
+                let cell = grid[index].as_ref().filter(|cell| !cell.is_frozen())?;
+
+                Some((cell.id, next.get(cell.x, cell.y)))
+            })
+            .collect()
+    }
+
+    /// Compute the next generation with `simulation::step` on the set of the alive positions,
+    /// independently of the neighbor links of the cells
+    fn compute_next_generation_simulation(&self) -> Vec<(Uuid, bool)> {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+        let boundary = self.get_boundary_mode();
+        let rule = self.get_rule();
+
+        let offsets = self.neighbor_offsets();
+
+        let current = self.alive_set();
+        let next =
+            simulation::step_with_offsets(&current, width, height, boundary, &rule, &offsets);
+
+        current
+            .symmetric_difference(&next)
+            .filter_map(|&(x, y)| {
+                let cell = self.find_cell(x, y).filter(|cell| !cell.is_frozen())?;
+
+                Some((cell.id, next.contains(&(x, y))))
+            })
+            .collect()
+    }
+
+    /// Whether a cell is relevant for the next generation, i.e. it is alive or is a neighbor of an alive cell.
+    /// The cached count of a cell is its number of alive neighbors, so the neighbors themselves are not visited,
+    /// which is slow on a crowded board.
+    fn is_relevant(cell: &Cell) -> bool {
+        cell.is_alive() || cell.alive_neighbor_count() > 0
+    }
+
+    /// Update the next generation of the board with the given list of cell ids and their future state.
+    /// Returns the net change of the population, the births minus the deaths.
+    /// It does not check whether the user is editing the cells, the generations go through `step` or `commit_generation`.
+    fn update_next_generation(&self, next_gen: &[(Uuid, bool)]) -> i64 {
+        let _edit = self.lock_edit();
+
+        parallel::run(self.cells.len(), || self.apply_next_generation(next_gen))
+    }
+
+    /// Apply the given list of cell ids and their future state, see `update_next_generation`.
+    /// The caller must hold the edit lock, see `lock_edit`.
+    fn apply_next_generation(&self, next_gen: &[(Uuid, bool)]) -> i64 {
+        let width = *lock(&self.width);
+
+        // Hold the generation during the whole update, so that a snapshot never sees a half-updated board
+        let mut generation = lock(&self.generation);
+
+        self.record_history(next_gen);
+        self.record_snapshot(*generation);
+        lock(&self.recorder).record(next_gen);
+
+        // The cells alive before the update get older, the ones dying are reset below
+        self.cells
+            .par_iter()
+            .for_each(|entry| entry.value().grow_older());
+
+        let changes: Vec<((u64, u64), bool)> = next_gen
+            .par_iter()
+            .filter_map(|(id, alive)| {
+                // A frozen cell keeps its state, even if the changes were computed before it was frozen
+                let cell = self.get_cell(*id).filter(|cell| !cell.is_frozen())?;
+
+                let changed = cell.is_alive() != *alive;
+
+                cell.set_alive(*alive);
+
+                changed.then_some(((cell.x, cell.y), *alive))
+            })
+            .collect();
+
+        // Counted from the cells that changed, without another pass over the board
+        let population_delta = changes
+            .iter()
+            .map(|&(_, alive)| if alive { 1 } else { -1 })
+            .sum();
+
+        // The cells are not edited meanwhile, so the version is the one right after the generation
+        *lock(&self.changed_indices) = Some((
+            self.get_version(),
+            changes
+                .iter()
+                .map(|&((x, y), _)| (y * width + x) as usize)
+                .collect(),
+        ));
+
+        self.record_changes(changes.into_iter().map(|(position, _)| position).collect());
+        self.record_population(*generation + 1);
+
+        *generation += 1;
+
+        population_delta
+    }
+
+    /// Compute and commit the next generation, unless the user is editing the cells.
+    /// Returns the list of cell ids with their new state, or `None` if the step was skipped.
+    /// Every generation of the board goes through it, so that none is computed during an edit.
+    pub fn step(&self) -> Option<Vec<(Uuid, bool)>> {
+        self.step_with_delta().map(|(_, next_gen)| next_gen)
+    }
+
+    /// Compute and commit the next generation like `step`.
+    /// Returns the net change of the population, the births minus the deaths, with the list of cell ids
+    /// with their new state, or `None` if the step was skipped.
+    pub fn step_with_delta(&self) -> Option<(i64, Vec<(Uuid, bool)>)> {
+        let _step = lock(&self.step_lock);
+
+        if self.is_editing() {
+            return None;
+        }
+
+        let next_gen = self.compute_next_generation();
+
+        let population_delta = self.update_next_generation(&next_gen);
+
+        Some((population_delta, next_gen))
+    }
+
+    /// Compute the next generation without committing it, stamped with the current generation and version of the cells,
+    /// e.g. to show a preview before `commit_generation`.
+    /// Returns `None` while the user is editing the cells, there is no generation to show meanwhile.
+    pub fn compute_generation_diff(&self) -> Option<GenerationDiff> {
+        // No generation is committed by `step` while the diff is computed
+        let _step = lock(&self.step_lock);
+
+        if self.is_editing() {
+            return None;
+        }
+
+        // Read before computing, so that an edit made meanwhile makes the diff stale instead of mislabeled
+        let generation = self.get_generation();
+        let version = self.get_version();
+
+        Some(GenerationDiff {
+            generation,
+            version,
+            changes: self.compute_next_generation(),
+        })
+    }
+
+    /// Commit a diff computed by `compute_generation_diff`, incrementing the generation.
+    /// Returns the net change of the population, the births minus the deaths,
+    /// or an error if the user is editing the cells, if the board is not at the generation of the diff anymore,
+    /// or if the cells were edited since the diff was computed.
+    pub fn commit_generation(&self, diff: &GenerationDiff) -> Result<i64, BoardError> {
+        let _step = lock(&self.step_lock);
+
+        if self.is_editing() {
+            return Err(BoardError::Editing);
+        }
+
+        // Hold the topology from the checks to the update, so that no edit slips in between
+        let _topology = lock(&self.topology);
+
+        let current = self.get_generation();
+
+        if diff.generation != current {
+            return Err(BoardError::StaleDiff {
+                computed: diff.generation,
+                current,
+            });
+        }
+
+        if diff.version != self.get_version() {
+            return Err(BoardError::EditedSinceDiff);
+        }
+
+        self.version.fetch_add(1, Ordering::SeqCst);
+
+        Ok(parallel::run(self.cells.len(), || {
+            self.apply_next_generation(&diff.changes)
+        }))
+    }
+
+    /// Start editing the cells, e.g. when the user presses the mouse button on the board.
+    /// The steps are skipped until `end_edit`, so that a stroke is never mixed with a generation,
+    /// and the step in progress, if any, is finished before returning.
+    /// The frontend calls it before changing the first cell of a stroke and calls `end_edit` once the stroke is over,
+    /// including when the pointer leaves the board.
+    pub fn begin_edit(&self) {
+        self.editing.store(true, Ordering::SeqCst);
+
+        drop(lock(&self.step_lock));
+    }
+
+    /// Stop editing the cells, the steps are computed again, see `begin_edit`
+    pub fn end_edit(&self) {
+        self.editing.store(false, Ordering::SeqCst);
+    }
+
+    /// Whether the user is editing the cells, see `begin_edit`
+    pub fn is_editing(&self) -> bool {
+        self.editing.load(Ordering::SeqCst)
+    }
+
+    /// Whether the board is stable, i.e. the next generation would not change any cell.
+    /// An empty board is stable.
+    pub fn is_stable(&self) -> bool {
+        self.compute_next_generation().is_empty()
+    }
+
+    /// Classify the cells by what the next generation does to them, without modifying the board.
+    /// Returns `(surviving, dying, born)`: the number of alive cells staying alive, of alive cells dying,
+    /// and of dead cells becoming alive.
+    pub fn classify_next(&self) -> (usize, usize, usize) {
+        let (dying, born) = self
+            .compute_next_generation()
+            .par_iter()
+            .filter_map(|(id, _)| self.get_cell(*id))
+            .map(|cell| if cell.is_alive() { (1, 0) } else { (0, 1) })
+            .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1));
+
+        (self.count_alive().saturating_sub(dying), dying, born)
+    }
+
+    /// Get the positions of the dead cells becoming alive in the next generation, sorted by `(x, y)`,
+    /// without modifying the board, e.g. to draw the births before committing them
+    pub fn upcoming_births(&self) -> Vec<(u64, u64)> {
+        self.upcoming_changes(true)
+    }
+
+    /// Get the positions of the alive cells dying in the next generation, sorted by `(x, y)`,
+    /// without modifying the board
+    pub fn upcoming_deaths(&self) -> Vec<(u64, u64)> {
+        self.upcoming_changes(false)
+    }
+
+    /// Get the positions of the cells whose state becomes the given one in the next generation, sorted by `(x, y)`
+    fn upcoming_changes(&self, alive: bool) -> Vec<(u64, u64)> {
+        let mut positions: Vec<(u64, u64)> = self
+            .compute_next_generation()
+            .par_iter()
+            .filter(|(_, next_alive)| *next_alive == alive)
+            .filter_map(|(id, _)| self.get_cell(*id))
+            .map(|cell| (cell.x, cell.y))
+            .collect();
+
+        positions.par_sort_unstable();
+
+        positions
+    }
+
+    /// Advance the board by `n` generations, see `step`.
+    /// The board stops early if the user starts editing the cells, and does not move while they are edited.
+    /// Returns the list of cell ids whose state differs from before the first generation, with their final state.
+    pub fn advance(&self, n: usize) -> Vec<(Uuid, bool)> {
+        let mut initial_states: HashMap<Uuid, bool> = HashMap::new();
+
+        for _ in 0..n {
+            let Some(next_gen) = self.step() else {
+                break;
+            };
+
+            // The state before the first change of a cell is the opposite of its first new state
+            for &(id, alive) in &next_gen {
+                initial_states.entry(id).or_insert(!alive);
+            }
+        }
+
+        initial_states
+            .into_par_iter()
+            .filter_map(|(id, was_alive)| {
+                let alive = self.get_cell(id)?.is_alive();
+
+                (alive != was_alive).then_some((id, alive))
+            })
+            .collect()
+    }
+
+    /// Advance the board until its generation reaches `target`, e.g. to reproduce a documented generation of a pattern.
+    /// Like `advance`, the board stops early if the user starts editing the cells.
+    /// Returns the list of cell ids whose state differs from before the first generation, with their final state,
+    /// or an error if the board is already at or past `target`.
+    pub fn step_to_generation(&self, target: usize) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let current = self.get_generation();
+
+        if target <= current {
+            return Err(BoardError::GenerationInPast { target, current });
+        }
+
+        Ok(self.advance(target - current))
+    }
+
+    /// Advance the board by `n` generations, keeping the positions of the alive cells every `sample_every` generations,
+    /// e.g. to play back a burst without drawing every generation.
+    /// Like `advance`, the board stops early if the user starts editing the cells, with the frames sampled so far.
+    /// Returns the positions of the alive cells at each multiple of `sample_every`, sorted by `(x, y)`,
+    /// or an error if `sample_every` is 0 or if there would be more than `MAX_SAMPLED_FRAMES` frames.
+    pub fn advance_sampled(
+        &self,
+        n: usize,
+        sample_every: usize,
+    ) -> Result<Vec<Vec<(u64, u64)>>, BoardError> {
+        if sample_every == 0 {
+            return Err(BoardError::InvalidConfig(
+                "The sampling interval must be at least 1 generation".to_string(),
+            ));
+        }
+
+        let frame_count = n / sample_every;
+
+        if frame_count > MAX_SAMPLED_FRAMES {
+            return Err(BoardError::InvalidConfig(format!(
+                "Sampling {n} generations every {sample_every} gives {frame_count} frames, the maximum is {MAX_SAMPLED_FRAMES}"
+            )));
+        }
+
+        let mut frames = Vec::with_capacity(frame_count);
+
+        for generation in 1..=n {
+            if self.step().is_none() {
+                break;
+            }
+
+            if generation % sample_every == 0 {
+                frames.push(self.alive_positions());
+            }
+        }
+
+        Ok(frames)
+    }
+
+    /// Preview the next `n` generations without modifying the board, e.g. to prefetch the frames of an animation.
+    /// The generations are computed with `simulation::step` on the set of the alive positions, the frozen cells
+    /// keeping their state, so the board is not copied.
+    /// Returns the list of cell ids with their new state for each generation, the earliest first,
+    /// or an error if `n` is larger than `MAX_SAMPLED_FRAMES`.
+    pub fn preview_n(&self, n: usize) -> Result<Vec<Vec<(Uuid, bool)>>, BoardError> {
+        if n > MAX_SAMPLED_FRAMES {
+            return Err(BoardError::InvalidConfig(format!(
+                "Cannot preview {n} generations, the maximum is {MAX_SAMPLED_FRAMES}"
+            )));
+        }
+
+        // Read under the topology lock, so that the preview does not start from a half-edited board
+        let (mut alive, frozen, width, height, boundary, rule, offsets) = {
+            let _topology = lock(&self.topology);
+
+            let frozen: HashSet<(u64, u64)> = self
+                .cells
+                .par_iter()
+                .filter(|entry| entry.value().is_frozen())
+                .map(|entry| (entry.value().x, entry.value().y))
+                .collect();
+
+            (
+                self.alive_set(),
+                frozen,
+                *lock(&self.width),
+                *lock(&self.height),
+                self.get_boundary_mode(),
+                self.get_rule(),
+                self.neighbor_offsets(),
+            )
+        };
+
+        let mut frames = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            let mut next =
+                simulation::step_with_offsets(&alive, width, height, boundary, &rule, &offsets);
+
+            for &position in &frozen {
+                if alive.contains(&position) {
+                    next.insert(position);
+                } else {
+                    next.remove(&position);
+                }
+            }
+
+            frames.push(
+                alive
+                    .symmetric_difference(&next)
+                    .filter_map(|position| {
+                        let id = *self.position_to_id.get(position)?;
+
+                        Some((id, next.contains(position)))
+                    })
+                    .collect(),
+            );
+
+            alive = next;
+        }
+
+        Ok(frames)
+    }
+
+    /// Start running the board on its own, computing a generation every `interval` on a separate thread.
+    /// `on_step` is called with the changed cells after each generation, and the run stops if it returns `false`.
+    /// No generation is computed while the user is editing the cells, see `begin_edit`.
+    /// Returns `false` and starts nothing if the board is already running.
+    pub fn start_auto<F>(self: &Arc<Self>, interval: Duration, mut on_step: F) -> bool
+    where
+        F: FnMut(&[(Uuid, bool)]) -> bool + Send + 'static,
+    {
+        let mut auto_thread = lock(&self.auto_thread);
+
+        if self.auto_running.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        // A previous run may still be finishing its last generation
+        if let Some(previous) = auto_thread.take() {
+            let _ = previous.join();
+        }
+
+        let board = Arc::clone(self);
+
+        *auto_thread = Some(thread::spawn(move || {
+            while board.auto_running.load(Ordering::SeqCst) {
+                // The generations are skipped while the user is editing the cells, not delayed
+                if let Some(next_gen) = board.step() {
+                    if !on_step(&next_gen) {
+                        board.auto_running.store(false, Ordering::SeqCst);
+                        break;
+                    }
+                }
+
+                // Parked instead of sleeping, so that `stop_auto` does not wait for the whole interval
+                let deadline = Instant::now() + interval;
+
+                while board.auto_running.load(Ordering::SeqCst) {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        break;
+                    }
+
+                    thread::park_timeout(deadline - now);
+                }
+            }
+        }));
+
+        true
+    }
+
+    /// Stop running the board on its own, waiting for the current generation to finish.
+    /// Returns `false` if the board was not running.
+    pub fn stop_auto(&self) -> bool {
+        let mut auto_thread = lock(&self.auto_thread);
+
+        let was_running = self.auto_running.swap(false, Ordering::SeqCst);
+
+        if let Some(handle) = auto_thread.take() {
+            handle.thread().unpark();
+
+            let _ = handle.join();
+        }
+
+        was_running
+    }
+
+    /// Whether the board is running on its own
+    pub fn is_auto_running(&self) -> bool {
+        self.auto_running.load(Ordering::SeqCst)
+    }
+
+    /// Compute the center of the recent activity of the board.
+    /// Each cell that flipped during the last generations is weighted by how recent the change is,
+    /// so the regions that just became active pull the center towards them.
+    /// Returns `None` if no cell changed recently.
+    pub fn activity_center(&self) -> Option<(f64, f64)> {
+        let activity_window = *lock(&self.activity_window);
+        let recent_changes = lock(&self.recent_changes);
+
+        let (total_weight, sum_x, sum_y) = recent_changes
+            .iter()
+            .enumerate()
+            .flat_map(|(age, changes)| {
+                let weight = (activity_window - age) as f64;
+
+                changes.iter().map(move |&(x, y)| (weight, x, y))
+            })
+            .fold((0.0, 0.0, 0.0), |(total, sum_x, sum_y), (weight, x, y)| {
+                (
+                    total + weight,
+                    sum_x + weight * x as f64,
+                    sum_y + weight * y as f64,
+                )
+            });
+
+        if total_weight == 0.0 {
+            return None;
+        }
+
+        Some((sum_x / total_weight, sum_y / total_weight))
+    }
+
+    /// Export the settings of the engine
+    pub fn export_config(&self) -> EngineConfig {
+        EngineConfig {
+            rule: self.get_rule(),
+            boundary: self.get_boundary_mode(),
+            activity_window: *lock(&self.activity_window),
+            backend: self.get_backend(),
+            neighbor_radius: self.get_radius(),
+            neighbor_mask: self.get_neighbor_mask(),
+            snapshot_interval: *lock(&self.snapshot_interval),
+            deterministic_ids: *lock(&self.deterministic_ids),
+            population_history_length: *lock(&self.population_history_length),
+        }
+    }
+
+    /// Import the settings of the engine.
+    /// The configuration is validated first, so either every setting is applied or none is.
+    pub fn import_config(&self, config: EngineConfig) -> Result<(), BoardError> {
+        config.validate().map_err(BoardError::InvalidConfig)?;
+
+        let _edit = self.lock_edit();
+
+        let links_changed = {
+            let mut rule = lock(&self.rule);
+            let mut boundary = lock(&self.boundary);
+            let mut neighbor_radius = lock(&self.neighbor_radius);
+            let mut neighbor_mask = lock(&self.neighbor_mask);
+            let mut activity_window = lock(&self.activity_window);
+            let mut recent_changes = lock(&self.recent_changes);
+
+            *rule = config.rule;
+            *lock(&self.backend) = config.backend;
+            *lock(&self.snapshot_interval) = config.snapshot_interval;
+            *lock(&self.deterministic_ids) = config.deterministic_ids;
+            *activity_window = config.activity_window;
+            recent_changes.truncate(config.activity_window);
+
+            let boundary_changed =
+                std::mem::replace(&mut *boundary, config.boundary) != config.boundary;
+            let radius_changed = std::mem::replace(&mut *neighbor_radius, config.neighbor_radius)
+                != config.neighbor_radius;
+            let mask_changed = *neighbor_mask != config.neighbor_mask;
+            *neighbor_mask = config.neighbor_mask;
+
+            boundary_changed || radius_changed || mask_changed
+        };
+
+        self.set_population_history_length(config.population_history_length)?;
+
+        if links_changed {
+            self.recompute_neighbors(None);
+        }
+
+        Ok(())
+    }
+
+    /// Step the board backward by `k` generations using the recorded history.
+    /// Returns `false` and leaves the board untouched if the history does not go back that far.
+    pub fn step_back_k(&self, k: usize) -> bool {
+        let _edit = self.lock_edit();
+        let mut generation = lock(&self.generation);
+
+        let steps: Vec<Vec<(Uuid, bool)>> = {
+            let mut history = lock(&self.history);
+
+            if k > history.len() {
+                return false;
+            }
+
+            history.drain(..k).collect()
+        };
+
+        // The most recent generation is undone first
+        for previous_states in &steps {
+            self.restore_states(previous_states);
+        }
+
+        *generation = generation.saturating_sub(k);
+
+        self.forget_after(*generation);
+
+        true
+    }
+
+    /// Undo the last generation using the recorded history.
+    /// Returns the list of cell ids that changed with their restored state,
+    /// or `None` if there is no generation left to undo.
+    pub fn undo(&self) -> Option<Vec<(Uuid, bool)>> {
+        let _edit = self.lock_edit();
+        let mut generation = lock(&self.generation);
+
+        let previous_states = lock(&self.history).pop_front()?;
+
+        let changed = self.restore_states(&previous_states);
+
+        *generation = generation.saturating_sub(1);
+
+        self.forget_after(*generation);
+
+        Some(changed)
+    }
+
+    /// Forget the changes, the snapshots, the populations and the recorded steps of the generations
+    /// after the given one, when the board went back to it
+    fn forget_after(&self, generation: usize) {
+        self.clear_changes();
+
+        lock(&self.snapshots).split_off(&(generation + 1));
+        lock(&self.population_history).retain(|&(recorded, _)| recorded <= generation);
+        lock(&self.recorder).truncate(generation);
+    }
+
+    /// Get the positions of the dead cells within `radius` of the given position that will be born in the next generation.
+    /// The next generation is only previewed, the board is not modified.
+    pub fn pending_births_near(&self, x: u64, y: u64, radius: u64) -> Vec<(u64, u64)> {
+        let mut births: Vec<(u64, u64)> = self
+            .compute_next_generation()
+            .par_iter()
+            .filter(|(_, alive)| *alive)
+            .filter_map(|(id, _)| self.get_cell(*id))
+            .filter(|cell| !cell.is_alive())
+            .filter(|cell| cell.x.abs_diff(x) <= radius && cell.y.abs_diff(y) <= radius)
+            .map(|cell| (cell.x, cell.y))
+            .collect();
+
+        births.sort_unstable();
+
+        births
+    }
+
+    /// Compare the board to a target pattern of the given size placed at the given origin.
+    /// Returns `(missing, extra)`: the number of alive cells of the pattern that are dead on the board,
+    /// and the number of alive cells on the board, inside the footprint of the pattern, that are not in the pattern.
+    pub fn compare_to_pattern(
+        &self,
+        pattern_width: u64,
+        pattern_height: u64,
+        pattern: &[(u64, u64)],
+        origin_x: u64,
+        origin_y: u64,
+    ) -> (usize, usize) {
+        let target: HashSet<(u64, u64)> = pattern
+            .iter()
+            .filter_map(|&(x, y)| Some((origin_x.checked_add(x)?, origin_y.checked_add(y)?)))
+            .collect();
+
+        let missing = target.len()
+            - target
+                .par_iter()
+                .filter(|&&(x, y)| self.find_cell(x, y).is_some_and(|cell| cell.is_alive()))
+                .count();
+
+        let extra = self
+            .cells
+            .par_iter()
+            .filter(|entry| {
+                let cell = entry.value();
+
+                cell.x >= origin_x
+                    && cell.x - origin_x < pattern_width
+                    && cell.y >= origin_y
+                    && cell.y - origin_y < pattern_height
+                    && cell.is_alive()
+                    && !target.contains(&(cell.x, cell.y))
+            })
+            .count();
+
+        (missing, extra)
+    }
+
+    /// Find the occurrences of a pattern on the board, e.g. to count the gliders.
+    /// The pattern is given by the positions of its alive cells, relative to its top left corner,
+    /// and its footprint is the rectangle from that corner to its last alive cells.
+    /// An occurrence must match the footprint exactly: the alive cells of the pattern are alive on the board,
+    /// and the other cells of the footprint are dead. The cells around the footprint are not looked at,
+    /// and the footprint must lie entirely on the board.
+    /// Returns the top left corners of the occurrences, sorted by `(x, y)`.
+    pub fn find_pattern(&self, pattern: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+        let pattern: HashSet<(u64, u64)> = pattern.into_iter().collect();
+
+        // Every occurrence puts the first cell of the pattern on an alive cell, so only those are tried
+        let Some(&(anchor_x, anchor_y)) = pattern.iter().min() else {
+            return Vec::new();
+        };
+
+        let pattern_width = pattern
+            .iter()
+            .map(|&(x, _)| x.saturating_add(1))
+            .max()
+            .unwrap_or(0);
+        let pattern_height = pattern
+            .iter()
+            .map(|&(_, y)| y.saturating_add(1))
+            .max()
+            .unwrap_or(0);
+
+        let (width, height) = self.dimensions();
+        let alive = self.alive_set();
+
+        let mut occurrences: Vec<(u64, u64)> = alive
+            .par_iter()
+            .filter_map(|&(x, y)| {
+                let origin_x = x.checked_sub(anchor_x)?;
+                let origin_y = y.checked_sub(anchor_y)?;
+
+                if origin_x.saturating_add(pattern_width) > width
+                    || origin_y.saturating_add(pattern_height) > height
+                {
+                    return None;
+                }
+
+                let matches = (0..pattern_width).all(|dx| {
+                    (0..pattern_height).all(|dy| {
+                        alive.contains(&(origin_x + dx, origin_y + dy))
+                            == pattern.contains(&(dx, dy))
+                    })
+                });
+
+                matches.then_some((origin_x, origin_y))
+            })
+            .collect();
+
+        occurrences.sort_unstable();
+
+        occurrences
+    }
+
+    /// Take a snapshot of the size, the generation and the alive cells of the board
+    fn snapshot(&self) -> SavedBoard {
+        // Holding the generation prevents a new generation from being applied while collecting the cells
+        let generation = lock(&self.generation);
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+
+        let mut alive: Vec<(u64, u64)> = self
+            .cells
+            .par_iter()
+            .filter(|entry| entry.value().is_alive())
+            .map(|entry| (entry.value().x, entry.value().y))
+            .collect();
+
+        alive.sort_unstable();
+
+        SavedBoard {
+            width,
+            height,
+            generation: *generation,
+            alive,
+        }
+    }
+
+    /// Save the board to a JSON file.
+    /// Only the positions of the alive cells are stored, with the size and the generation of the board.
+    pub fn save_to_file(&self, path: &str) -> Result<(), BoardError> {
+        let json = serde_json::to_string(&self.snapshot())
+            .map_err(|error| BoardError::File(format!("Failed to serialize the board: {error}")))?;
+
+        std::fs::write(path, json).map_err(|error| {
+            BoardError::File(format!("Failed to write the board to {path}: {error}"))
+        })
+    }
+
+    /// Take a snapshot of the whole board, with its generation, boundary mode, rule and neighborhood,
+    /// unlike `save_to_file` which only keeps the alive cells
+    pub fn to_snapshot(&self) -> BoardSnapshot {
+        let saved = self.snapshot();
+
+        BoardSnapshot {
+            width: saved.width,
+            height: saved.height,
+            generation: saved.generation,
+            boundary: self.get_boundary_mode(),
+            rule: self.get_rule(),
+            radius: self.get_radius(),
+            mask: self.get_neighbor_mask(),
+            alive: saved.alive,
+        }
+    }
+
+    /// Restore a board from a snapshot taken by `to_snapshot`.
+    /// The alive cells outside of the snapshot size are skipped.
+    /// The snapshot is validated first, so either the whole board is restored or it is left untouched.
+    /// Returns the id and the position of every cell on the new board,
+    /// or an error if the size, the neighborhood or the rule is invalid.
+    pub fn from_snapshot(
+        &self,
+        snapshot: BoardSnapshot,
+    ) -> Result<Vec<(Uuid, u64, u64)>, BoardError> {
+        check_dimensions(snapshot.width, snapshot.height)?;
+        config::validate_radius(snapshot.radius).map_err(BoardError::InvalidConfig)?;
+        snapshot
+            .mask
+            .validate(snapshot.radius)
+            .map_err(BoardError::InvalidConfig)?;
+        snapshot
+            .rule
+            .validate(snapshot.mask.offsets(snapshot.radius).len())
+            .map_err(BoardError::InvalidConfig)?;
+
+        let _step = lock(&self.step_lock);
+        let _edit = self.lock_edit();
+
+        *lock(&self.rule) = snapshot.rule;
+        *lock(&self.boundary) = snapshot.boundary;
+        *lock(&self.neighbor_radius) = snapshot.radius;
+        *lock(&self.neighbor_mask) = snapshot.mask;
+
+        // The neighbors are linked with the new neighborhood when the cells are rebuilt
+        self.rebuild_cells(snapshot.width, snapshot.height, &snapshot.alive);
+        self.set_generation(snapshot.generation);
+
+        Ok(self.get_cell_positions())
+    }
+
+    /// Render the board to a PNG file, drawing each alive cell as a black square of `cell_px` pixels
+    /// on a white background. The top left corner of the image is the cell at `(0, 0)`.
+    /// Returns an error if the cell size is 0, if the image would be too large or if the file cannot be written.
+    pub fn render_png(&self, path: &str, cell_px: u64) -> Result<(), BoardError> {
+        if cell_px == 0 {
+            return Err(BoardError::InvalidImage(
+                "The cells must be at least 1 pixel wide".to_string(),
+            ));
+        }
+
+        let snapshot = self.snapshot();
+
+        if snapshot.width == 0 || snapshot.height == 0 {
+            return Err(BoardError::InvalidImage("The board is empty".to_string()));
+        }
+
+        let image_size = |cells: u64| {
+            cells
+                .checked_mul(cell_px)
+                .and_then(|pixels| u32::try_from(pixels).ok())
+                .ok_or_else(|| {
+                    BoardError::InvalidImage(format!(
+                        "The image of {}x{} cells of {cell_px} pixels is too large",
+                        snapshot.width, snapshot.height
+                    ))
+                })
+        };
+
+        let (image_width, image_height) =
+            (image_size(snapshot.width)?, image_size(snapshot.height)?);
+
+        // Checked before allocating, as a legal board with large cells would not fit in memory
+        if u64::from(image_width) * u64::from(image_height) > MAX_IMAGE_PIXELS {
+            return Err(BoardError::InvalidImage(format!(
+                "The image of {image_width}x{image_height} pixels is larger than {MAX_IMAGE_PIXELS} pixels"
+            )));
+        }
+
+        let mut image = GrayImage::from_pixel(image_width, image_height, Luma([255]));
+
+        for &(x, y) in &snapshot.alive {
+            for py in y * cell_px..(y + 1) * cell_px {
+                for px in x * cell_px..(x + 1) * cell_px {
+                    // Both fit in a `u32`, the image size was checked above
+                    image.put_pixel(px as u32, py as u32, Luma([0]));
+                }
+            }
+        }
+
+        image
+            .save_with_format(path, ImageFormat::Png)
+            .map_err(|error| {
+                BoardError::File(format!("Failed to write the image to {path}: {error}"))
+            })
+    }
+
+    /// Load a board from a JSON file produced by `save_to_file`.
+    /// The board is recreated with the saved size, and the saved alive cells outside of it are skipped.
+    /// Returns the id and the position of every cell on the new board.
+    pub fn load_from_file(&self, path: &str) -> Result<Vec<(Uuid, u64, u64)>, BoardError> {
+        let json = std::fs::read_to_string(path).map_err(|error| {
+            BoardError::File(format!("Failed to read the board from {path}: {error}"))
+        })?;
+
+        let saved: SavedBoard = serde_json::from_str(&json)
+            .map_err(|error| BoardError::File(format!("Invalid board file {path}: {error}")))?;
+
+        let cells = self.create_with_cells(saved.width, saved.height, &saved.alive)?;
+
+        self.set_generation(saved.generation);
+
+        Ok(cells)
+    }
+
+    /// Set alive the cells at the given positions, relative to the given origin.
+    /// The positions outside the board are skipped.
+    /// Returns the list of cell ids that became alive.
+    pub fn place_cells(
+        &self,
+        cells: &[(u64, u64)],
+        origin_x: u64,
+        origin_y: u64,
+    ) -> Vec<(Uuid, bool)> {
+        let _edit = self.lock_edit();
+
+        cells
+            .par_iter()
+            .filter_map(|&(x, y)| {
+                let cell = self.find_cell(origin_x.checked_add(x)?, origin_y.checked_add(y)?)?;
+
+                let was_alive = cell.is_alive();
+
+                cell.set_alive(true);
+
+                (!was_alive).then_some((cell.id, true))
+            })
+            .collect()
+    }
+
+    /// Stamp a pattern onto the board, with its bounding box centered on the board.
+    /// A pattern larger than the board is aligned on the top left corner in that direction and clipped.
+    /// Returns the list of cell ids that became alive, and a warning if the pattern was clipped.
+    pub fn place_pattern_centered(
+        &self,
+        cells: &[(u64, u64)],
+    ) -> (Vec<(Uuid, bool)>, Option<String>) {
+        let (width, height) = self.dimensions();
+
+        let (Some(min_x), Some(min_y)) = (
+            cells.iter().map(|&(x, _)| x).min(),
+            cells.iter().map(|&(_, y)| y).min(),
+        ) else {
+            return (Vec::new(), None);
+        };
+
+        let pattern_width = cells.iter().map(|&(x, _)| x).max().unwrap_or(min_x) - min_x + 1;
+        let pattern_height = cells.iter().map(|&(_, y)| y).max().unwrap_or(min_y) - min_y + 1;
+
+        let cells: Vec<(u64, u64)> = cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+
+        let origin_x = width.saturating_sub(pattern_width) / 2;
+        let origin_y = height.saturating_sub(pattern_height) / 2;
+
+        let warning = (pattern_width > width || pattern_height > height).then(|| {
+            format!(
+                "The pattern of {pattern_width}x{pattern_height} cells is larger than the board of {width}x{height} cells, it was clipped"
+            )
+        });
+
+        (self.place_cells(&cells, origin_x, origin_y), warning)
+    }
+
+    /// Stamp a pattern in the RLE format onto the board, with its top left corner at the given origin.
+    /// Returns the list of cell ids that became alive, or an error if the pattern is invalid.
+    pub fn load_pattern_rle(
+        &self,
+        rle: &str,
+        origin_x: u64,
+        origin_y: u64,
+    ) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let (_, _, cells) = rle::parse_rle(rle).map_err(BoardError::InvalidPattern)?;
+
+        Ok(self.place_cells(&cells, origin_x, origin_y))
+    }
+
+    /// Stamp a pattern in the plaintext format (`.cells`) onto the board, with its top left corner at the given origin.
+    /// Returns the list of cell ids that became alive, or an error if the pattern is invalid.
+    pub fn load_pattern_cells(
+        &self,
+        text: &str,
+        origin_x: u64,
+        origin_y: u64,
+    ) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let (_, _, cells) = plaintext::parse_cells(text).map_err(BoardError::InvalidPattern)?;
+
+        Ok(self.place_cells(&cells, origin_x, origin_y))
+    }
+
+    /// Stamp a built-in pattern onto the board, with its top left corner at the given origin.
+    /// The cells outside the board are skipped.
+    /// Returns the list of cell ids that became alive.
+    pub fn place_pattern(
+        &self,
+        pattern: Pattern,
+        origin_x: u64,
+        origin_y: u64,
+    ) -> Vec<(Uuid, bool)> {
+        self.place_cells(&pattern.cells(), origin_x, origin_y)
+    }
+
+    /// Export the alive cells of the board in the RLE format, cropped to their bounding box
+    pub fn export_rle(&self) -> String {
+        let alive: Vec<(u64, u64)> = self
+            .cells
+            .par_iter()
+            .filter(|entry| entry.value().is_alive())
+            .map(|entry| (entry.value().x, entry.value().y))
+            .collect();
+
+        let rule = self.get_rule();
+
+        let (Some(min_x), Some(min_y)) = (
+            alive.iter().map(|&(x, _)| x).min(),
+            alive.iter().map(|&(_, y)| y).min(),
+        ) else {
+            return rle::write_rle(0, 0, &[], &rule);
+        };
+
+        let max_x = alive.iter().map(|&(x, _)| x).max().unwrap_or(min_x);
+        let max_y = alive.iter().map(|&(_, y)| y).max().unwrap_or(min_y);
+
+        let cells: Vec<(u64, u64)> = alive.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+
+        rle::write_rle(max_x - min_x + 1, max_y - min_y + 1, &cells, &rule)
+    }
+
+    /// Stamp a pattern in the Life 1.06 format onto the board.
+    /// The pattern is translated so that its minimum coordinates are at the given origin,
+    /// and the cells outside the board are skipped.
+    /// Returns the list of cell ids that became alive, or an error if the pattern is invalid.
+    pub fn load_pattern_life106(
+        &self,
+        text: &str,
+        origin_x: u64,
+        origin_y: u64,
+    ) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let cells = life106::parse_life106(text).map_err(BoardError::InvalidPattern)?;
+
+        Ok(self.place_cells(&life106::to_origin(&cells), origin_x, origin_y))
+    }
+
+    /// Export the alive cells of the board in the Life 1.06 format,
+    /// relative to the top left corner of their bounding box and sorted by `(x, y)`
+    pub fn export_life106(&self) -> String {
+        let alive = self.alive_set();
+
+        let min_x = alive.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let min_y = alive.iter().map(|&(_, y)| y).min().unwrap_or(0);
+
+        let mut cells: Vec<(i64, i64)> = alive
+            .iter()
+            .map(|&(x, y)| ((x - min_x) as i64, (y - min_y) as i64))
+            .collect();
+
+        cells.sort_unstable();
+
+        life106::write_life106(&cells)
+    }
+
+    /// Set each cell of the board alive with the probability `density`, clamped to `[0.0, 1.0]`.
+    /// With a seed the result is reproducible, otherwise the random generator is seeded from the system.
+    /// Returns the list of cell ids whose state changed, with their new state.
+    pub fn randomize(&self, density: f64, seed: Option<u64>) -> Vec<(Uuid, bool)> {
+        let density = if density.is_nan() {
+            0.0
+        } else {
+            density.clamp(0.0, 1.0)
+        };
+
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+
+        // The states are drawn in a fixed order of positions, so the same seed always gives the same board
+        let states: Vec<((u64, u64), bool)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|position| (position, rng.gen_bool(density)))
+            .collect();
+
+        let _edit = self.lock_edit();
+
+        states
+            .par_iter()
+            .filter_map(|&((x, y), alive)| {
+                let cell = self.find_cell(x, y)?;
+
+                let was_alive = cell.is_alive();
+
+                cell.set_alive(alive);
+
+                (was_alive != alive).then_some((cell.id, alive))
+            })
+            .collect()
+    }
+
+    /// Build a Hashlife board with the rule and the alive cells of the board.
+    /// Returns an error if the neighborhood is not the eight surrounding cells or if Hashlife does not support the rule.
+    pub fn to_hashlife(&self) -> Result<HashlifeBoard, BoardError> {
+        if self.get_radius() != DEFAULT_NEIGHBOR_RADIUS {
+            return Err(BoardError::InvalidConfig(
+                "Hashlife only supports a neighborhood radius of 1".to_string(),
+            ));
+        }
+
+        if self.get_neighbor_mask() != NeighborMask::Moore {
+            return Err(BoardError::InvalidConfig(
+                "Hashlife only supports the Moore neighbor mask".to_string(),
+            ));
+        }
+
+        let cells: Vec<(i64, i64)> = self
+            .alive_set()
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect();
+
+        HashlifeBoard::new(self.get_rule(), &cells).map_err(BoardError::InvalidConfig)
+    }
+
+    /// Advance the board by `steps` generations at once with the Hashlife algorithm.
+    /// The pattern is simulated on an unbounded plane: the edges neither stop nor wrap the cells,
+    /// and the cells which end up outside of the board are dropped.
+    /// The history of the board is lost.
+    /// Returns the list of cell ids that changed with their new state, or an error if the edges of the board
+    /// are not dead or if the board cannot be simulated with Hashlife, see `to_hashlife`.
+    pub fn advance_hashlife(&self, steps: u64) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        if self.get_boundary_mode() != BoundaryMode::Dead {
+            return Err(BoardError::InvalidConfig(
+                "Hashlife only supports dead edges".to_string(),
+            ));
+        }
+
+        if self.cells.par_iter().any(|entry| entry.value().is_frozen()) {
+            return Err(BoardError::InvalidConfig(
+                "Hashlife does not support the frozen cells".to_string(),
+            ));
+        }
+
+        let hashlife = self.to_hashlife()?;
+
+        hashlife.advance(steps).map_err(BoardError::InvalidConfig)?;
+
+        let alive: HashSet<(u64, u64)> = hashlife
+            .cells()
+            .into_iter()
+            .filter_map(|(x, y)| Some((u64::try_from(x).ok()?, u64::try_from(y).ok()?)))
+            .collect();
+
+        let _edit = self.lock_edit();
+        let mut generation = lock(&self.generation);
+
+        let changed = self.set_alive_set(&alive);
+
+        *generation = generation.saturating_add(usize::try_from(steps).unwrap_or(usize::MAX));
+
+        self.clear_changes();
+        self.clear_history();
+
+        Ok(changed)
+    }
+
+    /// Detect whether the board is periodic, i.e. it comes back to its current configuration.
+    /// Returns the smallest period up to `max_period`, or `None` if the configuration does not repeat
+    /// within `max_period` generations (e.g. a glider which translates).
+    /// The generations are simulated aside, the board is not modified.
+    pub fn detect_period(&self, max_period: usize) -> Option<usize> {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+        let boundary = self.get_boundary_mode();
+        let rule = self.get_rule();
+
+        let offsets = self.neighbor_offsets();
+
+        let initial = self.alive_set();
+        let mut current = initial.clone();
+
+        for period in 1..=max_period {
+            current =
+                simulation::step_with_offsets(&current, width, height, boundary, &rule, &offsets);
+
+            if current == initial {
+                return Some(period);
+            }
+        }
+
+        None
+    }
+
+    /// Advance the board until it is stable or periodic with a period up to `MAX_SETTLED_PERIOD`,
+    /// or until `max_generations` generations were computed.
+    /// The board settled once it comes back to one of its last `MAX_SETTLED_PERIOD` configurations,
+    /// recognized by their `state_hash` and confirmed by `detect_period`.
+    /// Like `advance`, the board stops early if the user starts editing the cells.
+    /// Returns the number of generations computed and whether the board settled.
+    pub fn run_until_stable(&self, max_generations: usize) -> (usize, bool) {
+        let mut recent_hashes = VecDeque::from([self.state_hash()]);
+
+        for computed in 1..=max_generations {
+            if self.step().is_none() {
+                return (computed - 1, false);
+            }
+
+            let hash = self.state_hash();
+
+            // A hash collision is ruled out before stopping, which only costs a check once the hash repeats
+            if recent_hashes.contains(&hash) && self.detect_period(MAX_SETTLED_PERIOD).is_some() {
+                return (computed, true);
+            }
+
+            recent_hashes.push_back(hash);
+
+            if recent_hashes.len() > MAX_SETTLED_PERIOD {
+                recent_hashes.pop_front();
+            }
+        }
+
+        (max_generations, false)
+    }
+
+    /// Look for a random soup that settles with at least `min_final_population` alive cells.
+    /// Each of the `samples` soups is a board of the given size with the settings of this board,
+    /// randomized with `SOUP_DENSITY` and a random seed, and run until it settles, see `run_until_stable`,
+    /// or for at most `SOUP_MAX_GENERATIONS` generations. The soups are run in parallel.
+    /// Returns the seed of the first soup found, which `randomize` turns back into the soup,
+    /// or `None` if no soup qualifies or if the search was cancelled, see `cancel_soup_search`.
+    pub fn soup_search(
+        &self,
+        width: u64,
+        height: u64,
+        samples: usize,
+        min_final_population: usize,
+    ) -> Result<Option<u64>, BoardError> {
+        self.soup_search_cancelled.store(false, Ordering::SeqCst);
+
+        let config = self.export_config();
+
+        let mut rng = StdRng::from_entropy();
+        let seeds: Vec<u64> = (0..samples).map(|_| rng.gen()).collect();
+
+        seeds
+            .par_iter()
+            .map(|&seed| {
+                if self.soup_search_cancelled.load(Ordering::SeqCst) {
+                    return Ok(None);
+                }
+
+                let soup = Board::new();
+
+                soup.import_config(config.clone())?;
+                soup.create_board(width, height)?;
+                soup.randomize(SOUP_DENSITY, Some(seed));
+
+                let settled = loop {
+                    if self.soup_search_cancelled.load(Ordering::SeqCst) {
+                        return Ok(None);
+                    }
+
+                    let generation = soup.get_generation();
+
+                    if generation >= SOUP_MAX_GENERATIONS {
+                        break false;
+                    }
+
+                    let steps = SOUP_CANCEL_CHECK_INTERVAL.min(SOUP_MAX_GENERATIONS - generation);
+
+                    if soup.run_until_stable(steps).1 {
+                        break true;
+                    }
+                };
+
+                let population = soup.stats().population;
+
+                Ok((settled && population >= min_final_population).then_some(seed))
+            })
+            .find_first(|result| !matches!(result, Ok(None)))
+            .unwrap_or(Ok(None))
+    }
+
+    /// Stop the running soup search, which then returns `None`
+    pub fn cancel_soup_search(&self) {
+        self.soup_search_cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Normalize the corners of an inclusive rectangle and clamp it to the board.
+    /// Returns `(min_x, min_y, max_x, max_y)`, or `None` if the rectangle is outside the board.
+    fn clamp_region(&self, x0: u64, y0: u64, x1: u64, y1: u64) -> Option<(u64, u64, u64, u64)> {
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+
+        let min_x = x0.min(x1);
+        let min_y = y0.min(y1);
+        let max_x = x0.max(x1).min(width.checked_sub(1)?);
+        let max_y = y0.max(y1).min(height.checked_sub(1)?);
+
+        (min_x <= max_x && min_y <= max_y).then_some((min_x, min_y, max_x, max_y))
+    }
+
+    /// Set the state of every cell in the inclusive rectangle between the given corners.
+    /// Returns the list of cell ids that changed, with their new state.
+    fn set_region(&self, x0: u64, y0: u64, x1: u64, y1: u64, alive: bool) -> Vec<(Uuid, bool)> {
+        let Some((min_x, min_y, max_x, max_y)) = self.clamp_region(x0, y0, x1, y1) else {
+            return Vec::new();
+        };
+
+        let _edit = self.lock_edit();
+
+        (min_x..=max_x)
+            .into_par_iter()
+            .flat_map_iter(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter_map(|(x, y)| {
+                let cell = self.find_cell(x, y)?;
+
+                let was_alive = cell.is_alive();
+
+                cell.set_alive(alive);
+
+                (was_alive != alive).then_some((cell.id, alive))
+            })
+            .collect()
+    }
+
+    /// Kill every cell in the inclusive rectangle between the given corners.
+    /// The corners can be given in any order and are clamped to the board.
+    /// Returns the list of cell ids that changed, with their new state.
+    pub fn clear_region(&self, x0: u64, y0: u64, x1: u64, y1: u64) -> Vec<(Uuid, bool)> {
+        self.set_region(x0, y0, x1, y1, false)
+    }
+
+    /// Set alive every cell in the inclusive rectangle between the given corners.
+    /// The corners can be given in any order and are clamped to the board.
+    /// Returns the list of cell ids that changed, with their new state.
+    pub fn fill_region(&self, x0: u64, y0: u64, x1: u64, y1: u64) -> Vec<(Uuid, bool)> {
+        self.set_region(x0, y0, x1, y1, true)
+    }
+
+    /// Get the positions of the alive cells in the inclusive rectangle between the given corners,
+    /// e.g. to only fetch the cells visible on screen, sorted by `(x, y)`.
+    /// The corners can be given in any order and are clamped to the board.
+    pub fn alive_in_region(&self, x0: u64, y0: u64, x1: u64, y1: u64) -> Vec<(u64, u64)> {
+        let Some((min_x, min_y, max_x, max_y)) = self.clamp_region(x0, y0, x1, y1) else {
+            return Vec::new();
+        };
+
+        (min_x..=max_x)
+            .into_par_iter()
+            .flat_map_iter(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter(|&(x, y)| self.find_cell(x, y).is_some_and(|cell| cell.is_alive()))
+            .collect()
+    }
+
+    /// Advance only the cells in the inclusive rectangle between the given corners by one generation,
+    /// the cells outside being left unchanged, e.g. to experiment on a part of the board.
+    /// The cells on the edges of the rectangle see the current states of their neighbors outside.
+    /// The corners can be given in any order and are clamped to the board.
+    /// Like the other edits of a region, it is not a generation of the board: the generation, the history
+    /// and the ages are unchanged.
+    /// Returns the list of cell ids that changed, with their new state.
+    pub fn step_region(&self, x0: u64, y0: u64, x1: u64, y1: u64) -> Vec<(Uuid, bool)> {
+        let Some((min_x, min_y, max_x, max_y)) = self.clamp_region(x0, y0, x1, y1) else {
+            return Vec::new();
+        };
+
+        let _edit = self.lock_edit();
+
+        let rule = self.get_rule();
+
+        // Every future state is computed before any is applied, so the region sees a single generation
+        let changes: Vec<(Arc<Cell>, bool)> = (min_x..=max_x)
+            .into_par_iter()
+            .flat_map_iter(|x| (min_y..=max_y).map(move |y| (x, y)))
+            .filter_map(|(x, y)| {
+                let cell = self.find_cell(x, y)?;
+
+                let future_alive = cell.compute_future_state(&rule);
+
+                (future_alive != cell.is_alive()).then_some((cell, future_alive))
+            })
+            .collect();
+
+        changes
+            .into_par_iter()
+            .map(|(cell, alive)| {
+                cell.set_alive(alive);
+
+                (cell.id, alive)
+            })
+            .collect()
+    }
+
+    /// Reflect the alive cells across the center axis of the board.
+    /// If `horizontal` is true the board is flipped left to right, otherwise top to bottom.
+    /// Returns the list of cell ids that changed, with their new state.
+    pub fn mirror(&self, horizontal: bool) -> Vec<(Uuid, bool)> {
+        let _edit = self.lock_edit();
+
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+
+        let mirrored = self
+            .alive_set()
+            .into_iter()
+            .map(|(x, y)| {
+                if horizontal {
+                    (width - 1 - x, y)
+                } else {
+                    (x, height - 1 - y)
+                }
+            })
+            .collect();
+
+        self.set_alive_set(&mirrored)
+    }
+
+    /// Rotate the alive cells by 90 degrees about the center of the board.
+    /// Returns the list of cell ids that changed, with their new state,
+    /// or an error if the board is not square.
+    pub fn rotate(&self, clockwise: bool) -> Result<Vec<(Uuid, bool)>, BoardError> {
+        let _edit = self.lock_edit();
+
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+
+        if width != height {
+            return Err(BoardError::NotSquare { width, height });
+        }
+
+        let rotated = self
+            .alive_set()
+            .into_iter()
+            .map(|(x, y)| {
+                if clockwise {
+                    (height - 1 - y, x)
+                } else {
+                    (y, width - 1 - x)
+                }
+            })
+            .collect();
+
+        Ok(self.set_alive_set(&rotated))
+    }
+
+    /// Move every alive cell by the given offset.
+    /// If the edges of the board wrap around the cells pushed off the board come back on the other side,
+    /// otherwise they are dropped.
+    /// Returns the list of cell ids that changed, with their new state.
+    pub fn translate(&self, dx: i64, dy: i64) -> Vec<(Uuid, bool)> {
+        let _edit = self.lock_edit();
+
+        let width = *lock(&self.width);
+        let height = *lock(&self.height);
+        let wrap = self.get_wrap();
+
+        if width == 0 || height == 0 {
+            return Vec::new();
+        }
+
+        let translated = self
+            .alive_set()
+            .into_iter()
+            .filter_map(|(x, y)| {
+                if wrap {
+                    // Reduce the offsets first so that adding them cannot overflow
+                    // The size of a board fits in an `i64`, it has at most `MAX_CELLS` cells
+                    let dx = dx.rem_euclid(width as i64) as u64;
+                    let dy = dy.rem_euclid(height as i64) as u64;
+
+                    Some(((x + dx) % width, (y + dy) % height))
+                } else {
+                    let x = Cell::offset_position(x, dx)?;
+                    let y = Cell::offset_position(y, dy)?;
+
+                    (x < width && y < height).then_some((x, y))
+                }
+            })
+            .collect();
+
+        self.set_alive_set(&translated)
+    }
+
+    /// Set the state of the cells with the given ids.
+    /// The unknown ids are skipped.
+    /// Returns the list of cell ids that were found, with their new state.
+    pub fn update_cells(&self, updates: &[(Uuid, bool)]) -> Vec<(Uuid, bool)> {
+        let _edit = self.lock_edit();
+
+        updates
+            .par_iter()
+            .filter_map(|&(id, alive)| {
+                let cell = self.get_cell(id)?;
+
+                cell.set_alive(alive);
+
+                Some((id, alive))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The first coordinate beyond what a `u32` can hold
+    const BEYOND_U32: u64 = 1 << 32;
+
+    /// Create a board of the given size with the cells at the given positions alive
+    fn board_with(width: u64, height: u64, alive: &[(u64, u64)]) -> Board {
+        let board = Board::new();
+
+        board.create_with_cells(width, height, alive).unwrap();
+
+        board
+    }
+
+    /// Assert that the cached alive neighbor count of every cell matches a recount of its neighbors
+    fn assert_counts_match(board: &Board) {
+        for cell in board.get_cells() {
+            assert_eq!(
+                cell.alive_neighbor_count(),
+                cell.count_alive_neighbors(),
+                "cell at ({}, {})",
+                cell.x,
+                cell.y
+            );
+        }
+    }
+
+    #[test]
+    fn cached_neighbor_counts_follow_a_glider() {
+        let board = board_with(10, 10, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+
+        assert_counts_match(&board);
+
+        for _ in 0..8 {
+            board.step();
+
+            assert_counts_match(&board);
+        }
+
+        board.toggle_at(5, 5);
+        board.kill_board();
+
+        assert_counts_match(&board);
+    }
+
+    #[test]
+    fn cached_neighbor_counts_survive_edits_while_relinking() {
+        let board = Arc::new(board_with(16, 16, &[]));
+
+        let editor = {
+            let board = Arc::clone(&board);
+
+            thread::spawn(move || {
+                for i in 0..2000 {
+                    board.toggle_at(i % 16, (i / 16) % 16);
+                }
+            })
+        };
+
+        // Each change of the edges links the neighbors of every cell again
+        for i in 0..50 {
+            board.set_boundary_mode(if i % 2 == 0 {
+                BoundaryMode::Toroidal
+            } else {
+                BoundaryMode::Reflective
+            });
+        }
+
+        editor.join().unwrap();
+
+        assert_counts_match(&board);
+    }
+
+    #[test]
+    fn steps_are_skipped_while_editing() {
+        let blinker = [(1, 0), (1, 1), (1, 2)];
+        let board = board_with(5, 5, &blinker);
+
+        board.begin_edit();
+
+        assert_eq!(board.step(), None);
+        assert_eq!(board.step_with_delta(), None);
+        assert!(board.advance(3).is_empty());
+        assert_eq!(board.advance_sampled(4, 2), Ok(Vec::new()));
+        assert_eq!(board.step_to_generation(2), Ok(Vec::new()));
+        assert_eq!(board.run_until_stable(10), (0, false));
+        assert_eq!(board.compute_generation_diff(), None);
+
+        assert_eq!(board.get_generation(), 0);
+        assert_eq!(board.alive_positions(), blinker.to_vec());
+
+        board.end_edit();
+
+        assert!(board.step().is_some());
+        assert_eq!(board.get_generation(), 1);
+        assert_eq!(board.alive_positions(), vec![(0, 1), (1, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn diffs_are_rejected_after_an_edit() {
+        let blinker = [(1, 0), (1, 1), (1, 2)];
+        let board = board_with(5, 5, &blinker);
+
+        let diff = board.compute_generation_diff().unwrap();
+
+        // A stroke lands between the computation and the commit
+        board.toggle_at(4, 4).unwrap();
+
+        assert_eq!(
+            board.commit_generation(&diff),
+            Err(BoardError::EditedSinceDiff)
+        );
+        assert_eq!(board.get_generation(), 0);
+        assert_eq!(
+            board.alive_positions(),
+            vec![(1, 0), (1, 1), (1, 2), (4, 4)]
+        );
+
+        let diff = board.compute_generation_diff().unwrap();
+        board.set_frozen(board.find_cell(1, 0).unwrap().id, true);
+
+        assert_eq!(
+            board.commit_generation(&diff),
+            Err(BoardError::EditedSinceDiff)
+        );
+
+        let diff = board.compute_generation_diff().unwrap();
+
+        assert!(board.commit_generation(&diff).is_ok());
+        assert_eq!(board.get_generation(), 1);
+
+        // A diff cannot be committed twice
+        assert!(board.commit_generation(&diff).is_err());
+    }
+
+    #[test]
+    fn backends_agree_on_random_soups() {
+        let rules = ["B3/S23", "B36/S23", "B0/S23", "B0123478/S01234678"];
+        let boundaries = [
+            BoundaryMode::Dead,
+            BoundaryMode::Toroidal,
+            BoundaryMode::Reflective,
+        ];
+        let backends = [Backend::Cells, Backend::Bitset, Backend::Simulation];
+
+        for (seed, rule) in rules.iter().enumerate() {
+            let rule = Rule::parse(rule).unwrap();
+
+            for boundary in boundaries {
+                let soup = Board::new();
+                soup.create_board(24, 17).unwrap();
+                soup.randomize(0.35, Some(seed as u64));
+
+                let boards: Vec<Board> = backends
+                    .iter()
+                    .map(|&backend| {
+                        let board = board_with(24, 17, &soup.alive_positions());
+
+                        board
+                            .set_rule(rule.birth.clone(), rule.survival.clone())
+                            .unwrap();
+                        board.set_boundary_mode(boundary);
+                        board.set_backend(backend);
+
+                        board
+                    })
+                    .collect();
+
+                for generation in 1..=8 {
+                    for board in &boards {
+                        board.step().unwrap();
+                    }
+
+                    let expected = boards[0].alive_positions();
+
+                    for (board, backend) in boards.iter().zip(backends).skip(1) {
+                        assert_eq!(
+                            board.alive_positions(),
+                            expected,
+                            "{backend:?} with {rule} and {boundary:?} at generation {generation}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_boards_stay_empty_unless_b0() {
+        for backend in [Backend::Cells, Backend::Bitset, Backend::Simulation] {
+            let board = board_with(6, 4, &[]);
+            board.set_backend(backend);
+
+            assert_eq!(board.step(), Some(Vec::new()), "{backend:?}");
+            assert_eq!(board.get_generation(), 1);
+            assert!(board.alive_positions().is_empty());
+
+            // Under B0 every cell of an empty board is born
+            board.set_rule(vec![0], vec![]).unwrap();
+
+            assert_eq!(board.step().map(|changes| changes.len()), Some(24));
+            assert_eq!(board.get_generation(), 2);
+            assert_eq!(board.alive_positions().len(), 24, "{backend:?}");
+        }
+    }
+
+    #[test]
+    fn relevant_cells_follow_the_edits() {
+        let gun = Pattern::GosperGliderGun.cells();
+        let boards = [Backend::Cells, Backend::Bitset].map(|backend| {
+            let board = board_with(48, 32, &[]);
+            board.set_backend(backend);
+            board.place_cells(&gun, 1, 1);
+
+            board
+        });
+
+        for generation in 1..=90 {
+            match generation {
+                // Cells edited between two generations are not among the cells changed by the last one
+                30 => boards.iter().for_each(|board| {
+                    board.toggle_at(40, 25);
+                    board.toggle_at(41, 25);
+                    board.toggle_at(42, 25);
+                }),
+                50 => boards.iter().for_each(|board| {
+                    board.set_frozen(board.find_cell(41, 25).unwrap().id, true);
+                }),
+                70 => boards.iter().for_each(|board| {
+                    board.set_rule(vec![3, 6], vec![2, 3]).unwrap();
+                }),
+                _ => {}
+            }
+
+            for board in &boards {
+                board.step().unwrap();
+            }
+
+            assert_eq!(
+                boards[0].alive_positions(),
+                boards[1].alive_positions(),
+                "generation {generation}"
+            );
+        }
+    }
+
+    #[test]
+    fn preview_leaves_the_board_unchanged() {
+        let glider = [(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)];
+        let board = board_with(8, 8, &glider);
+        board.set_frozen(board.find_cell(2, 2).unwrap().id, true);
+
+        let cells = board.get_cell_positions();
+
+        let frames = board.preview_n(6).unwrap();
+
+        assert_eq!(frames.len(), 6);
+        assert_eq!(board.get_generation(), 0);
+        assert_eq!(board.alive_positions(), glider.to_vec());
+        assert_eq!(board.get_cell_positions(), cells);
+
+        // The frames are the generations the board goes through afterwards, frozen cell included
+        for frame in frames {
+            let mut expected = board.step().unwrap();
+            let mut frame = frame;
+
+            expected.sort_unstable();
+            frame.sort_unstable();
+
+            assert_eq!(frame, expected);
+        }
+
+        assert!(board.preview_n(MAX_SAMPLED_FRAMES + 1).is_err());
+    }
+
+    #[test]
+    fn stepping_back_restores_the_past_generation() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let board = board_with(12, 12, &glider);
+        board.set_snapshot_interval(1).unwrap();
+        board.start_recording(100).unwrap();
+
+        let mut past = Vec::new();
+
+        for _ in 0..10 {
+            past.push((board.alive_positions(), board.population_history()));
+            board.step().unwrap();
+        }
+
+        assert!(board.step_back_k(3));
+
+        let (alive, population_history) = &past[7];
+
+        assert_eq!(board.get_generation(), 7);
+        assert_eq!(&board.alive_positions(), alive);
+        assert_eq!(&board.population_history(), population_history);
+        assert_eq!(board.stop_recording(), 7);
+        assert!(board.restore_generation(8).is_err());
+
+        assert!(board.undo().is_some());
+        assert_eq!(board.get_generation(), 6);
+        assert_eq!(board.alive_positions(), past[6].0);
+        assert_eq!(board.export_recording().len(), 6);
+
+        assert!(!board.step_back_k(7));
+        assert_eq!(board.get_generation(), 6);
+    }
+
+    #[test]
+    fn previews_leave_the_board_unchanged() {
+        let blinker = [(1, 0), (1, 1), (1, 2)];
+        let board = board_with(5, 5, &blinker);
+
+        let mut changes = board.compute_next_generation();
+        changes.sort_unstable();
+
+        let mut expected = vec![
+            (board.find_cell(0, 1).unwrap().id, true),
+            (board.find_cell(2, 1).unwrap().id, true),
+            (board.find_cell(1, 0).unwrap().id, false),
+            (board.find_cell(1, 2).unwrap().id, false),
+        ];
+        expected.sort_unstable();
+
+        assert_eq!(changes, expected);
+
+        let diff = board.compute_generation_diff().unwrap();
+
+        assert_eq!(diff.generation, 0);
+        assert_eq!(diff.changes.len(), 4);
+        assert_eq!(board.get_generation(), 0);
+        assert_eq!(board.alive_positions(), blinker.to_vec());
+        assert!(board.population_history().is_empty());
+    }
+
+    #[test]
+    fn activity_center_sits_in_the_active_region() {
+        // A blinker around (30, 10) and a block, which never changes, far from it
+        let board = board_with(
+            40,
+            40,
+            &[
+                (29, 10),
+                (30, 10),
+                (31, 10),
+                (2, 30),
+                (3, 30),
+                (2, 31),
+                (3, 31),
+            ],
+        );
+
+        assert_eq!(board.activity_center(), None);
+
+        for _ in 0..3 {
+            board.step().unwrap();
+        }
+
+        let (x, y) = board.activity_center().unwrap();
+
+        assert!((29.0..=31.0).contains(&x), "x = {x}");
+        assert!((9.0..=11.0).contains(&y), "y = {y}");
+
+        // The block alone never changes, so a board of still lifes has no activity
+        let still = board_with(40, 40, &[(2, 30), (3, 30), (2, 31), (3, 31)]);
+        still.step().unwrap();
+
+        assert_eq!(still.activity_center(), None);
+    }
+
+    #[test]
+    fn config_round_trips() {
+        let config = EngineConfig {
+            rule: Rule::parse("B36/S23").unwrap(),
+            boundary: BoundaryMode::Toroidal,
+            activity_window: 5,
+            backend: Backend::Bitset,
+            neighbor_radius: 2,
+            neighbor_mask: NeighborMask::VonNeumann,
+            snapshot_interval: 3,
+            deterministic_ids: true,
+            population_history_length: 50,
+        };
+        assert_ne!(config, EngineConfig::default());
+
+        let board = board_with(10, 10, &[]);
+        board.import_config(config.clone()).unwrap();
+
+        assert_eq!(board.export_config(), config);
+
+        let json = serde_json::to_string(&board.export_config()).unwrap();
+        let copy = board_with(10, 10, &[]);
+        copy.import_config(serde_json::from_str(&json).unwrap())
+            .unwrap();
+
+        assert_eq!(copy.export_config(), config);
+
+        // Eight neighbors are more than the four of the mask within radius 1, so nothing is applied
+        let invalid = EngineConfig {
+            rule: Rule::parse("B8/S23").unwrap(),
+            boundary: BoundaryMode::Reflective,
+            neighbor_radius: 1,
+            ..config.clone()
+        };
+
+        assert!(board.import_config(invalid).is_err());
+        assert_eq!(board.export_config(), config);
+    }
+
+    #[test]
+    fn pending_births_are_localized() {
+        // Two horizontal blinkers, each giving birth above and below its center
+        let board = board_with(
+            30,
+            30,
+            &[(5, 5), (6, 5), (7, 5), (20, 20), (21, 20), (22, 20)],
+        );
+
+        assert_eq!(board.pending_births_near(6, 5, 2), vec![(6, 4), (6, 6)]);
+        assert_eq!(board.pending_births_near(6, 5, 0), Vec::new());
+
+        // A radius reaching beyond the edges is clipped to the board
+        assert_eq!(
+            board.pending_births_near(0, 0, 100),
+            vec![(6, 4), (6, 6), (21, 19), (21, 21)]
+        );
+
+        assert_eq!(board.get_generation(), 0);
+        assert_eq!(board.count_alive(), 6);
+    }
+
+    #[test]
+    fn compare_a_partial_pattern_to_its_target() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        // Three cells of the glider at (4, 2), a stray cell inside its footprint and one outside
+        let board = board_with(10, 10, &[(5, 2), (6, 3), (4, 4), (4, 2), (9, 9)]);
+
+        assert_eq!(board.compare_to_pattern(3, 3, &glider, 4, 2), (2, 1));
+
+        board.toggle_at(5, 4).unwrap();
+        board.toggle_at(6, 4).unwrap();
+        board.toggle_at(4, 2).unwrap();
+
+        assert_eq!(board.compare_to_pattern(3, 3, &glider, 4, 2), (0, 0));
+
+        // Shifted by one cell, only two cells of the bottom row still match
+        assert_eq!(board.compare_to_pattern(3, 3, &glider, 5, 2), (3, 2));
+    }
+
+    #[test]
+    fn glider_wraps_around_the_edges() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        // Near the bottom right corner, the glider crosses both edges within a few generations
+        let at = |shift: u64| {
+            let mut cells: Vec<(u64, u64)> = glider
+                .iter()
+                .map(|&(x, y)| ((x + 5 + shift) % 8, (y + 5 + shift) % 8))
+                .collect();
+            cells.sort_unstable();
+
+            cells
+        };
+
+        let board = board_with(8, 8, &at(0));
+        board.set_wrap(true);
+
+        assert!(board.get_wrap());
+
+        for shift in 1..=8 {
+            for _ in 0..4 {
+                board.step().unwrap();
+            }
+
+            assert_eq!(
+                board.alive_positions(),
+                at(shift),
+                "after {} generations",
+                4 * shift
+            );
+        }
+    }
+
+    #[test]
+    fn still_block_has_an_empty_diff() {
+        let block = [(2, 2), (2, 3), (3, 2), (3, 3)];
+
+        for backend in [Backend::Cells, Backend::Bitset, Backend::Simulation] {
+            let board = board_with(6, 6, &block);
+            board.set_backend(backend);
+
+            assert_eq!(board.compute_next_generation(), Vec::new(), "{backend:?}");
+            assert_eq!(board.step(), Some(Vec::new()), "{backend:?}");
+            assert_eq!(board.alive_positions(), block.to_vec());
+        }
+    }
+
+    #[test]
+    fn still_lifes_are_stable() {
+        let blinker = [(1, 2), (2, 2), (3, 2)];
+
+        assert!(board_with(6, 6, &[]).is_stable());
+        assert!(board_with(6, 6, &[(2, 2), (2, 3), (3, 2), (3, 3)]).is_stable());
+
+        let board = board_with(6, 6, &blinker);
+
+        assert!(!board.is_stable());
+        assert_eq!(board.alive_positions(), blinker.to_vec());
+        assert_eq!(board.get_generation(), 0);
+    }
+
+    #[test]
+    fn detect_period_of_oscillators() {
+        let cases = [
+            (vec![(1, 2), (2, 2), (3, 2)], Some(2)),
+            (vec![(2, 2), (2, 3), (3, 2), (3, 3)], Some(1)),
+            // A glider comes back to its shape but not to its position
+            (vec![(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)], None),
+        ];
+
+        for (alive, period) in cases {
+            let board = board_with(20, 20, &alive);
+
+            assert_eq!(board.detect_period(10), period, "{alive:?}");
+            assert_eq!(board.alive_positions(), alive);
+            assert_eq!(board.get_generation(), 0);
+        }
+    }
+
+    #[test]
+    fn fill_region_sets_the_rectangle_alive() {
+        let board = board_with(10, 10, &[(3, 3)]);
+
+        // The cell already alive is not reported as changed
+        assert_eq!(board.fill_region(2, 2, 4, 4).len(), 8);
+        assert_eq!(board.count_alive(), 9);
+        assert_eq!(board.alive_in_region(2, 2, 4, 4).len(), 9);
+
+        // The corners are swapped and clamped to the board
+        let board = board_with(10, 10, &[]);
+
+        assert_eq!(board.fill_region(100, 9, 7, 8).len(), 6);
+        assert_eq!(
+            board.alive_positions(),
+            vec![(7, 8), (7, 9), (8, 8), (8, 9), (9, 8), (9, 9)]
+        );
+    }
+
+    #[test]
+    fn glider_matches_the_reference_across_a_resize() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let board = board_with(10, 10, &glider);
+
+        let mut reference: HashSet<(u64, u64)> = glider.into_iter().collect();
+        let (mut width, mut height) = (10, 10);
+
+        for generation in 1..=20 {
+            match generation {
+                // The glider heads towards the edges, which move away before it reaches them
+                6 => {
+                    board.resize(20, 20).unwrap();
+                    (width, height) = (20, 20);
+                }
+                // A blinker set alive through `update_cells`, among the cells created by the resize
+                8 => {
+                    for (x, y) in [(15, 3), (15, 4), (15, 5)] {
+                        let cell = board.find_cell(x, y).unwrap();
+                        board.update_cells(&[(cell.id, true)]);
+                        reference.insert((x, y));
+                    }
+                }
+                _ => {}
+            }
+
+            board.step().unwrap();
+            reference = simulation::step(
+                &reference,
+                width,
+                height,
+                BoundaryMode::Dead,
+                &Rule::default(),
+            );
+
+            let mut expected: Vec<(u64, u64)> = reference.iter().copied().collect();
+            expected.sort_unstable();
+
+            assert_eq!(board.alive_positions(), expected, "generation {generation}");
+            assert_counts_match(&board);
+        }
+    }
+
+    #[test]
+    fn mirroring_twice_restores_the_board() {
+        let glider = [(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)];
+        let board = board_with(7, 5, &glider);
+
+        for horizontal in [true, false] {
+            board.mirror(horizontal);
+
+            assert_eq!(board.count_alive(), glider.len());
+            assert_ne!(board.alive_positions(), glider.to_vec());
+
+            board.mirror(horizontal);
+
+            assert_eq!(board.alive_positions(), glider.to_vec());
+        }
+
+        board.mirror(true);
+
+        assert_eq!(
+            board.alive_positions(),
+            vec![(4, 1), (4, 2), (5, 0), (5, 2), (6, 2)]
+        );
+    }
+
+    #[test]
+    fn four_rotations_restore_the_board() {
+        let glider = [(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)];
+        let board = board_with(7, 7, &glider);
+
+        board.rotate(true).unwrap();
+
+        assert_eq!(
+            board.alive_positions(),
+            vec![(4, 0), (4, 1), (4, 2), (5, 2), (6, 1)]
+        );
+
+        for _ in 0..3 {
+            board.rotate(true).unwrap();
+        }
+
+        assert_eq!(board.alive_positions(), glider.to_vec());
+
+        board.rotate(true).unwrap();
+        board.rotate(false).unwrap();
+
+        assert_eq!(board.alive_positions(), glider.to_vec());
+    }
+
+    #[test]
+    fn rotating_a_rectangle_is_rejected() {
+        let glider = [(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)];
+        let board = board_with(7, 5, &glider);
+
+        assert_eq!(
+            board.rotate(true),
+            Err(BoardError::NotSquare {
+                width: 7,
+                height: 5
+            })
+        );
+        assert_eq!(board.alive_positions(), glider.to_vec());
+    }
+
+    #[test]
+    fn seeded_boards_are_identical() {
+        let seeded = |seed: u64| {
+            let board = Board::new();
+            board.set_deterministic_ids(true);
+            board.create_board(32, 32).unwrap();
+            board.randomize(0.4, Some(seed));
+
+            let mut cells = board.get_cell_positions();
+            cells.sort_unstable();
+
+            (board.alive_positions(), cells)
+        };
+
+        let (alive, cells) = seeded(7);
+
+        assert!(!alive.is_empty());
+        assert_eq!(seeded(7), (alive.clone(), cells.clone()));
+
+        let (other_alive, other_cells) = seeded(8);
+
+        assert_ne!(other_alive, alive);
+        assert_eq!(other_cells, cells);
+    }
+
+    #[test]
+    fn boundary_modes_differ_on_a_corner_cell() {
+        // Under B1/S, the cells with exactly one alive neighbor are born and the alive cell dies
+        let expected = [
+            (BoundaryMode::Dead, vec![(0, 1), (1, 0), (1, 1)]),
+            (
+                BoundaryMode::Toroidal,
+                vec![
+                    (0, 1),
+                    (0, 4),
+                    (1, 0),
+                    (1, 1),
+                    (1, 4),
+                    (4, 0),
+                    (4, 1),
+                    (4, 4),
+                ],
+            ),
+            // The corner cell is its own reflection, so its edge neighbors see it twice and are not born
+            (BoundaryMode::Reflective, vec![(1, 1)]),
+        ];
+
+        assert_eq!(BoundaryMode::default(), BoundaryMode::Dead);
+
+        for (boundary, alive) in expected {
+            for backend in [Backend::Cells, Backend::Bitset, Backend::Simulation] {
+                let board = board_with(5, 5, &[(0, 0)]);
+                board.set_rule(vec![1], vec![]).unwrap();
+                board.set_boundary_mode(boundary);
+                board.set_backend(backend);
+
+                board.step().unwrap();
+
+                assert_eq!(
+                    board.alive_positions(),
+                    alive,
+                    "{boundary:?} on {backend:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn snapshot_bitmap_decodes_to_the_alive_cells() {
+        // A width that is not a multiple of 8, so that the rows straddle the bytes
+        let board = board_with(11, 5, &[(0, 0), (10, 0), (1, 2), (2, 3), (0, 4), (10, 4)]);
+
+        board.step();
+
+        let (width, height, bytes) = board.snapshot_bitmap();
+
+        assert_eq!((width, height), (11, 5));
+        assert_eq!(bytes.len(), 7);
+
+        let decoded: Vec<(u64, u64)> = (0..width)
+            .flat_map(|x| (0..height).map(move |y| (x, y)))
+            .filter(|&(x, y)| {
+                let i = (y * width + x) as usize;
+
+                bytes[i / 8] >> (i % 8) & 1 == 1
+            })
+            .collect();
+
+        assert!(!decoded.is_empty());
+        assert_eq!(decoded, board.alive_positions());
+    }
+
+    #[test]
+    fn snapshot_round_trips() {
+        let board = board_with(12, 9, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2), (11, 8)]);
+
+        board.set_rule(vec![3, 6], vec![2, 3]).unwrap();
+        board.set_boundary_mode(BoundaryMode::Toroidal);
+
+        for _ in 0..5 {
+            board.step();
+        }
+
+        let snapshot = board.to_snapshot();
+
+        // Through JSON too, as the snapshots are saved and sent to the frontend
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let parsed: BoardSnapshot = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, snapshot);
+
+        let restored = Board::new();
+
+        restored.from_snapshot(parsed).unwrap();
+
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert_eq!(restored.get_generation(), 5);
+        assert_counts_match(&restored);
+
+        // Both boards run on identically
+        for _ in 0..10 {
+            board.step();
+            restored.step();
+        }
+
+        assert_eq!(restored.to_snapshot(), board.to_snapshot());
+    }
+
+    #[test]
+    fn invalid_snapshot_leaves_the_board_untouched() {
+        let board = board_with(6, 6, &[(1, 2), (2, 2), (3, 2)]);
+        let before = board.to_snapshot();
+
+        // Larger than Life with 24 neighbors, then a rule the 12 von Neumann neighbors cannot reach
+        let source = board_with(8, 7, &[(4, 4)]);
+
+        source.set_radius(2).unwrap();
+        source.set_rule(vec![20], vec![2, 3]).unwrap();
+
+        let mut snapshot = source.to_snapshot();
+
+        assert_eq!(snapshot.radius, 2);
+
+        snapshot.mask = NeighborMask::VonNeumann;
+
+        assert!(matches!(
+            board.from_snapshot(snapshot.clone()),
+            Err(BoardError::InvalidConfig(_))
+        ));
+        assert_eq!(board.to_snapshot(), before);
+
+        let too_large = BoardSnapshot {
+            width: u64::MAX,
+            ..source.to_snapshot()
+        };
+
+        assert!(board.from_snapshot(too_large).is_err());
+        assert_eq!(board.to_snapshot(), before);
+
+        board.from_snapshot(source.to_snapshot()).unwrap();
+
+        assert_eq!(board.to_snapshot(), source.to_snapshot());
+        assert_eq!(board.neighbor_offsets().len(), 24);
+        assert_counts_match(&board);
+    }
+
+    #[test]
+    fn matrix_follows_the_edits_and_the_generations() {
+        // A horizontal blinker on a board wider than tall, so that the rows and the columns cannot be swapped
+        let board = board_with(7, 4, &[(2, 1), (3, 1), (4, 1)]);
+
+        let to_positions = |rows: &[Vec<bool>]| -> Vec<(u64, u64)> {
+            let mut positions: Vec<(u64, u64)> = rows
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, &alive)| alive)
+                        .map(move |(x, _)| (x as u64, y as u64))
+                })
+                .collect();
+
+            positions.sort_unstable();
+
+            positions
+        };
+
+        let rows = board.to_matrix();
+
+        assert_eq!(rows.len() as u64, board.dimensions().1);
+        assert!(rows
+            .iter()
+            .all(|row| row.len() as u64 == board.dimensions().0));
+        assert_eq!(to_positions(&rows), vec![(2, 1), (3, 1), (4, 1)]);
+
+        board.step();
+
+        assert_eq!(
+            to_positions(&board.to_matrix()),
+            vec![(3, 0), (3, 1), (3, 2)]
+        );
+
+        board.toggle_at(6, 3);
+
+        let rows = board.to_matrix();
+
+        assert!(rows[3][6]);
+        assert_eq!(to_positions(&rows), board.alive_positions());
+
+        // The matrix sets the same board back
+        board.kill_board();
+
+        assert!(!board.set_from_matrix(rows.clone()).unwrap().is_empty());
+        assert_eq!(board.to_matrix(), rows);
+    }
+
+    #[test]
+    fn kill_board_forgets_the_past() {
+        // A still block, whose cells grow older, next to a blinker
+        let board = board_with(
+            10,
+            6,
+            &[(1, 1), (2, 1), (1, 2), (2, 2), (6, 2), (7, 2), (8, 2)],
+        );
+
+        for _ in 0..4 {
+            board.step();
+        }
+
+        assert_eq!(board.get_generation(), 4);
+        assert!(!board.population_history().is_empty());
+        assert!(board.get_cells().iter().any(|cell| cell.age() > 0));
+
+        board.kill_board();
+
+        assert_eq!(board.get_generation(), 0);
+        assert_eq!(board.count_alive(), 0);
+        assert!(board.population_history().is_empty());
+        assert!(board.get_cells().iter().all(|cell| cell.age() == 0));
+        assert_counts_match(&board);
+
+        // A cell set alive again starts from 0
+        board.toggle_at(1, 1);
+
+        assert_eq!(
+            board.get_cells().iter().map(|cell| cell.age()).max(),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn degenerate_sizes_do_not_panic() {
+        for (width, height) in [(0, 0), (1, 1), (0, 5), (5, 0), (1, 5), (5, 1)] {
+            for boundary in [
+                BoundaryMode::Dead,
+                BoundaryMode::Toroidal,
+                BoundaryMode::Reflective,
+            ] {
+                for backend in [Backend::Cells, Backend::Bitset, Backend::Simulation] {
+                    let board = board_with(width, height, &[(0, 0)]);
+                    board.set_boundary_mode(boundary);
+                    board.set_backend(backend);
+
+                    let context = format!("{width}x{height} {boundary:?} on {backend:?}");
+
+                    assert_eq!(board.dimensions(), (width, height), "{context}");
+                    assert_eq!(
+                        board.count_alive() as u64,
+                        width.min(height).min(1),
+                        "{context}"
+                    );
+
+                    board.step();
+                    board.toggle_at(0, 0);
+                    board.compute_next_generation();
+                    board.upcoming_births();
+                    board.stats();
+                    board.centroid();
+                    board.export_rle();
+
+                    assert_eq!(board.to_matrix().len() as u64, height, "{context}");
+                    assert_eq!(
+                        board.snapshot_bitmap().2.len() as u64,
+                        (width * height).div_ceil(8),
+                        "{context}"
+                    );
+                    assert_counts_match(&board);
+
+                    board.resize(height, width).unwrap();
+
+                    assert_eq!(board.dimensions(), (height, width), "{context}");
+
+                    board.step();
+                    board.kill_board();
+                    board.dispose();
+
+                    assert_eq!(board.dimensions(), (0, 0), "{context}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn absurd_sizes_are_rejected() {
+        let board = board_with(3, 3, &[(1, 1)]);
+
+        for (width, height) in [
+            (MAX_CELLS + 1, 1),
+            (1, MAX_CELLS + 1),
+            (1 << 13, 1 << 12),
+            (u64::MAX, 2),
+            (u64::MAX, u64::MAX),
+        ] {
+            assert!(matches!(
+                board.create_board(width, height),
+                Err(BoardError::InvalidDimensions { .. })
+            ));
+            assert!(board.resize(width, height).is_err());
+        }
+
+        // The board is left as it was
+        assert_eq!(board.dimensions(), (3, 3));
+        assert_eq!(board.alive_positions(), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn frozen_wall_blocks_a_glider() {
+        const GLIDER: [(u64, u64); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        const WALL: u64 = 7;
+
+        for backend in [Backend::Cells, Backend::Bitset, Backend::Simulation] {
+            let reached_beyond = |walled: bool| {
+                let board = board_with(16, 16, &GLIDER);
+                board.set_backend(backend);
+
+                if walled {
+                    // A column of frozen dead cells, never born however many alive neighbors they have
+                    for (id, x, _) in board.get_cell_positions() {
+                        if x == WALL {
+                            assert!(board.set_frozen(id, true));
+                        }
+                    }
+                }
+
+                (0..40).any(|_| {
+                    board.step();
+
+                    board.alive_positions().iter().any(|&(x, _)| x >= WALL)
+                })
+            };
+
+            assert!(reached_beyond(false), "{backend:?}");
+            assert!(!reached_beyond(true), "{backend:?}");
+        }
+    }
+
+    #[test]
+    fn life106_round_trips() {
+        let glider = [(5, 6), (6, 4), (6, 6), (7, 5), (7, 6)];
+        let board = board_with(12, 10, &glider);
+
+        let text = board.export_life106();
+
+        assert!(text.starts_with("#Life 1.06\n0 2\n"));
+
+        // The export is relative to the bounding box, so the pattern is stamped back at its top left corner
+        let copy = board_with(12, 10, &[]);
+
+        assert_eq!(copy.load_pattern_life106(&text, 5, 4).unwrap().len(), 5);
+        assert_eq!(copy.alive_positions(), glider.to_vec());
+        assert_eq!(copy.export_life106(), text);
+    }
+
+    #[test]
+    fn find_pattern_counts_the_gliders() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        let at = |origin_x: u64, origin_y: u64| glider.map(|(x, y)| (origin_x + x, origin_y + y));
+
+        let mut alive: Vec<(u64, u64)> = [at(1, 1), at(10, 12), at(14, 2)].concat();
+
+        // Right of the footprint of the first glider, which is not looked at
+        alive.push((4, 1));
+
+        // Inside the footprint of the third glider, which therefore does not match
+        alive.push((14, 2));
+
+        let board = board_with(20, 20, &alive);
+
+        assert_eq!(board.find_pattern(glider.to_vec()), vec![(1, 1), (10, 12)]);
+        assert_eq!(board.find_pattern(Vec::new()), Vec::new());
+
+        // A glider cut by the edge of the board is not an occurrence
+        let board = board_with(20, 20, &at(18, 0));
+
+        assert_eq!(board.find_pattern(glider.to_vec()), Vec::new());
+    }
+
+    #[test]
+    fn shrink_to_fit_trims_around_the_alive_cells() {
+        let board = board_with(20, 15, &[(9, 6), (10, 7), (8, 8), (9, 8), (10, 8)]);
+
+        for _ in 0..4 {
+            board.step();
+        }
+
+        // The glider moved by one cell down and right
+        assert_eq!(board.shrink_to_fit(1), Ok((5, 5)));
+        assert_eq!(board.dimensions(), (5, 5));
+        assert_eq!(
+            board.alive_positions(),
+            vec![(1, 3), (2, 1), (2, 3), (3, 2), (3, 3)]
+        );
+        assert_eq!(board.get_generation(), 4);
+        assert_counts_match(&board);
+
+        assert!(board.shrink_to_fit(u64::MAX).is_err());
+        assert_eq!(board.dimensions(), (5, 5));
+
+        board.kill_board();
+
+        assert_eq!(board.shrink_to_fit(3), Ok((0, 0)));
+        assert_eq!(board.dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn classify_the_next_generation_of_a_blinker() {
+        let blinker = [(1, 2), (2, 2), (3, 2)];
+        let board = board_with(5, 5, &blinker);
+
+        // The middle cell survives, the ends die and the cells above and below the middle are born
+        assert_eq!(board.classify_next(), (1, 2, 2));
+        assert_eq!(board.alive_positions(), blinker.to_vec());
+        assert_eq!(board.get_generation(), 0);
+
+        assert_eq!(board_with(5, 5, &[]).classify_next(), (0, 0, 0));
+    }
+
+    #[test]
+    fn upcoming_changes_match_the_committed_diff() {
+        for backend in [Backend::Cells, Backend::Bitset, Backend::Simulation] {
+            let board = board_with(16, 16, &[]);
+            board.set_backend(backend);
+            board.randomize(0.35, Some(7));
+
+            let positions: HashMap<Uuid, (u64, u64)> = board
+                .get_cell_positions()
+                .into_iter()
+                .map(|(id, x, y)| (id, (x, y)))
+                .collect();
+
+            for _ in 0..5 {
+                let births = board.upcoming_births();
+                let deaths = board.upcoming_deaths();
+
+                let diff = board.compute_generation_diff().unwrap();
+
+                let changed = |alive: bool| {
+                    let mut changed: Vec<(u64, u64)> = diff
+                        .changes
+                        .iter()
+                        .filter(|&&(_, next_alive)| next_alive == alive)
+                        .map(|(id, _)| positions[id])
+                        .collect();
+
+                    changed.sort_unstable();
+
+                    changed
+                };
+
+                assert_eq!(births, changed(true), "{backend:?}");
+                assert_eq!(deaths, changed(false), "{backend:?}");
+                assert!(!births.is_empty() || !deaths.is_empty());
+
+                board.commit_generation(&diff).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn centroid_of_the_alive_cells() {
+        assert_eq!(board_with(6, 6, &[]).centroid(), None);
+
+        assert_eq!(
+            board_with(6, 6, &[(2, 2), (2, 3), (3, 2), (3, 3)]).centroid(),
+            Some((2.5, 2.5))
+        );
+
+        // The glider is heavier at its bottom right
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+        assert_eq!(board_with(6, 6, &glider).centroid(), Some((1.2, 1.4)));
+
+        let board = board_with(6, 6, &glider);
+        board.kill_board();
+
+        assert_eq!(board.centroid(), None);
+    }
+
+    #[test]
+    fn step_to_a_past_generation_is_rejected() {
+        let board = board_with(6, 6, &[(1, 2), (2, 2), (3, 2)]);
+
+        board.step_to_generation(3).unwrap();
+
+        assert_eq!(board.get_generation(), 3);
+        assert_eq!(board.alive_positions(), vec![(2, 1), (2, 2), (2, 3)]);
+
+        for target in [0, 3] {
+            assert_eq!(
+                board.step_to_generation(target),
+                Err(BoardError::GenerationInPast { target, current: 3 })
+            );
+        }
+
+        assert_eq!(board.get_generation(), 3);
+    }
+
+    #[test]
+    fn run_until_stable_counts_the_generations() {
+        // A blinker already at generation 500 settles after one period
+        let board = board_with(6, 6, &[(1, 2), (2, 2), (3, 2)]);
+        board.advance(500);
+
+        assert_eq!(board.run_until_stable(100), (2, true));
+        assert_eq!(board.get_generation(), 502);
+
+        let block = board_with(6, 6, &[(2, 2), (2, 3), (3, 2), (3, 3)]);
+
+        assert_eq!(block.run_until_stable(100), (1, true));
+
+        // A glider on a torus only comes back after crossing the whole board
+        let glider = board_with(30, 30, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        glider.set_boundary_mode(BoundaryMode::Toroidal);
+        glider.advance(7);
+
+        assert_eq!(glider.run_until_stable(50), (50, false));
+        assert_eq!(glider.get_generation(), 57);
+    }
+
+    #[test]
+    fn render_png_limits_the_image_size() {
+        let board = board_with(100, 100, &[(0, 0), (99, 99)]);
+
+        // 200000x200000 pixels, each side fitting in a `u32` but not the whole image in memory
+        assert!(matches!(
+            board.render_png("unused.png", 2000),
+            Err(BoardError::InvalidImage(_))
+        ));
+        assert!(matches!(
+            board.render_png("unused.png", 0),
+            Err(BoardError::InvalidImage(_))
+        ));
+
+        let path = std::env::temp_dir().join(format!("board-{}.png", std::process::id()));
+        let path = path.to_string_lossy();
+
+        board.render_png(&path, 3).unwrap();
+
+        let image = image::open(&*path).unwrap().into_luma8();
+
+        assert_eq!(image.dimensions(), (300, 300));
+        assert_eq!(image.get_pixel(2, 2), &Luma([0]));
+        assert_eq!(image.get_pixel(3, 3), &Luma([255]));
+        assert_eq!(image.get_pixel(299, 299), &Luma([0]));
+
+        std::fs::remove_file(&*path).unwrap();
+    }
+
+    #[test]
+    fn find_cell_beyond_u32() {
+        let board = board_with(4, 4, &[(0, 0)]);
+
+        assert!(board.find_cell(0, 0).is_some());
+        // Truncated to 32 bits, these positions would be `(0, 0)`
+        assert!(board.find_cell(BEYOND_U32, 0).is_none());
+        assert!(board.find_cell(0, BEYOND_U32).is_none());
+        assert!(board.find_cell(u64::MAX, u64::MAX).is_none());
+    }
+
+    #[test]
+    fn place_cells_beyond_u32() {
+        let board = board_with(4, 4, &[]);
+
+        assert!(board
+            .place_cells(&[(0, 0), (1, 1)], BEYOND_U32, 0)
+            .is_empty());
+        assert!(board.place_cells(&[(0, 0)], 0, BEYOND_U32 + 1).is_empty());
+        // The positions overflowing a `u64` are skipped too
+        assert!(board.place_cells(&[(2, 0)], u64::MAX - 1, 0).is_empty());
+        // A relative position beyond `u32::MAX` is not truncated onto the board either
+        assert!(board.place_cells(&[(BEYOND_U32 + 1, 2)], 0, 0).is_empty());
+        assert!(board.is_empty());
+
+        assert_eq!(board.place_cells(&[(1, 2)], 0, 0).len(), 1);
+        assert_eq!(board.alive_positions(), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn compare_to_pattern_beyond_u32() {
+        let board = board_with(4, 4, &[(0, 0), (1, 0)]);
+        let pattern = [(0, 0), (1, 0)];
 
-        self.increment_generation();
+        assert_eq!(board.compare_to_pattern(2, 1, &pattern, 0, 0), (0, 0));
+        // The footprint is far from the board, so every cell of the pattern is missing and none is extra
+        assert_eq!(
+            board.compare_to_pattern(2, 1, &pattern, BEYOND_U32, 0),
+            (2, 0)
+        );
+        assert_eq!(
+            board.compare_to_pattern(2, 1, &pattern, 0, BEYOND_U32),
+            (2, 0)
+        );
+        // The cells overflowing a `u64` cannot be on the board, they are not counted
+        assert_eq!(
+            board.compare_to_pattern(2, 1, &pattern, u64::MAX, 0),
+            (1, 0)
+        );
     }
 }
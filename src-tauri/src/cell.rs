@@ -1,5 +1,7 @@
+use crate::error::lock;
+use crate::rule::Rule;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, Weak};
 use uuid::Uuid;
 
@@ -9,87 +11,271 @@ pub struct Cell {
     /// The id of the cell
     pub id: Uuid,
 
-    /// Whether the cell is alive or not
-    pub alive: Mutex<bool>,
+    /// Whether the cell is alive or not.
+    /// Private, so that every change goes through `set_alive` and keeps the neighbor counts up to date.
+    alive: Mutex<bool>,
 
-    /// The x position of the cell
-    pub x: usize,
+    /// The x position of the cell, in a fixed-size type so that the coordinates behave the same on every target
+    pub x: u64,
 
     /// The y position of the cell
-    pub y: usize,
+    pub y: u64,
+
+    /// The number of generations the cell has been alive for
+    age: Mutex<usize>,
+
+    /// The list of neighbors of the cell.
+    /// A list rather than a map keeps the cells small with a large neighborhood radius.
+    neighbors: Mutex<Vec<Weak<Cell>>>,
+
+    /// The number of alive neighbors of the cell, updated whenever a neighbor changes state
+    alive_neighbor_count: Mutex<usize>,
 
-    /// The list of neighbors of the cell
-    neighbors: Mutex<HashMap<(usize, usize), Weak<Cell>>>,
+    /// Whether the cell is frozen, its state being kept by the generations. It can still be edited.
+    frozen: Mutex<bool>,
+
+    /// The number of alive cells of the board, shared by its cells and updated whenever one of them changes state
+    population: Arc<AtomicUsize>,
 }
 
 impl Cell {
-    /// Create a new cell
-    pub fn new(alive: bool, x: usize, y: usize) -> Arc<Self> {
+    /// Create a new cell with a random id, counted in the given population if it is alive
+    pub fn new(alive: bool, x: u64, y: u64, population: &Arc<AtomicUsize>) -> Arc<Self> {
+        Self::with_id(Uuid::new_v4(), alive, x, y, population)
+    }
+
+    /// Create a new cell with the given id, counted in the given population if it is alive
+    pub fn with_id(
+        id: Uuid,
+        alive: bool,
+        x: u64,
+        y: u64,
+        population: &Arc<AtomicUsize>,
+    ) -> Arc<Self> {
+        if alive {
+            population.fetch_add(1, Ordering::Relaxed);
+        }
+
         Arc::new(Self {
-            id: Uuid::new_v4(),
+            id,
             alive: Mutex::new(alive),
             x,
             y,
-            neighbors: Mutex::new(HashMap::new()),
+            age: Mutex::new(0),
+            neighbors: Mutex::new(Vec::new()),
+            alive_neighbor_count: Mutex::new(0),
+            frozen: Mutex::new(false),
+            population: Arc::clone(population),
         })
     }
 
+    /// Whether the cell is alive or not
+    pub fn is_alive(&self) -> bool {
+        *lock(&self.alive)
+    }
+
     /// Set the alive state of the cell
     /// `true` if the cell is alive, `false` otherwise
-    pub fn set_alive(&self, alive: bool) {
-        let mut alive_ref = self.alive.lock().unwrap();
+    /// The age of the cell is reset when it dies.
+    /// If the state changes, the alive neighbor counts of the neighbors are updated.
+    /// Only the board changes the states, holding its edit lock so that the neighbors are not linked meanwhile.
+    pub(crate) fn set_alive(&self, alive: bool) {
+        let changed = {
+            let mut alive_ref = lock(&self.alive);
 
-        *alive_ref = alive;
+            std::mem::replace(&mut *alive_ref, alive) != alive
+        };
+
+        if changed {
+            self.state_changed(alive);
+        }
     }
 
-    /// Add a neighbor to the cell
-    pub fn add_neighbor(&self, neighbor_x: usize, neighbor_y: usize, neighbor: Arc<Cell>) {
-        let mut neighbors = self.neighbors.lock().unwrap();
+    /// Flip the alive state of the cell, see `set_alive`.
+    /// Returns the new state of the cell.
+    pub(crate) fn toggle_alive(&self) -> bool {
+        let alive = {
+            let mut alive_ref = lock(&self.alive);
+
+            *alive_ref = !*alive_ref;
+
+            *alive_ref
+        };
 
-        neighbors.insert((neighbor_x, neighbor_y), Arc::downgrade(&neighbor));
+        self.state_changed(alive);
+
+        alive
+    }
+
+    /// Reset the age of the cell if it died and update the population and the alive neighbor counts of its neighbors
+    fn state_changed(&self, alive: bool) {
+        if alive {
+            self.population.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.population.fetch_sub(1, Ordering::Relaxed);
+
+            *lock(&self.age) = 0;
+        }
+
+        for neighbor in self.get_neighbors() {
+            let mut count = lock(&neighbor.alive_neighbor_count);
+
+            if alive {
+                *count += 1;
+            } else {
+                // The count cannot be 0 if this cell was alive, unless the links changed meanwhile
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Get the number of generations the cell has been alive for
+    pub fn age(&self) -> usize {
+        *lock(&self.age)
+    }
+
+    /// Make the cell one generation older if it is alive
+    pub fn grow_older(&self) {
+        if self.is_alive() {
+            *lock(&self.age) += 1;
+        }
+    }
+
+    /// Add a neighbor to the cell.
+    /// A neighbor added several times counts several times, e.g. the reflections of a cell on a reflective board.
+    /// The state of the neighbor must not change meanwhile, the board links the neighbors under its topology lock.
+    pub fn add_neighbor(&self, neighbor: &Arc<Cell>) {
+        let neighbor_alive = neighbor.is_alive();
+
+        lock(&self.neighbors).push(Arc::downgrade(neighbor));
+
+        if neighbor_alive {
+            *lock(&self.alive_neighbor_count) += 1;
+        }
+    }
+
+    /// Remove all the neighbors of the cell
+    pub fn clear_neighbors(&self) {
+        lock(&self.neighbors).clear();
+
+        *lock(&self.alive_neighbor_count) = 0;
     }
 
     /// Get the list of neighbors of the cell
     pub fn get_neighbors(&self) -> Vec<Arc<Cell>> {
-        let neighbors = self.neighbors.lock().unwrap();
+        let neighbors = lock(&self.neighbors);
 
         neighbors
             .par_iter()
-            .filter_map(|(_, weak_neighbor)| weak_neighbor.upgrade()) // Upgrade the weak reference to a strong reference
+            .filter_map(|weak_neighbor| weak_neighbor.upgrade()) // Upgrade the weak reference to a strong reference
             .collect()
     }
 
-    /// Get the number of alive neighbors of the cell
+    /// Get the cached number of alive neighbors of the cell
+    pub fn alive_neighbor_count(&self) -> usize {
+        *lock(&self.alive_neighbor_count)
+    }
+
+    /// Count the alive neighbors of the cell by checking every neighbor.
+    /// Slower than `alive_neighbor_count`, useful to check the cached count.
     pub fn count_alive_neighbors(&self) -> usize {
-        let neighbors = self.neighbors.lock().unwrap();
+        let neighbors = lock(&self.neighbors);
 
         neighbors
             .par_iter()
-            .filter_map(|(_, weak_neighbor)| weak_neighbor.upgrade()) // Upgrade the weak reference to a strong reference
-            .filter(|neighbor| *neighbor.alive.lock().unwrap()) // Filter out the neighbors that are not alive
+            .filter_map(|weak_neighbor| weak_neighbor.upgrade()) // Upgrade the weak reference to a strong reference
+            .filter(|neighbor| neighbor.is_alive()) // Filter out the neighbors that are not alive
             .count()
     }
 
     /// Calculates an offset position relative to the cell position and an offset.
-    /// Returns `Some(usize)` if the offset position is valid, `None` otherwise.
-    /// A position is valid if it does not overflow the `usize` type.
-    pub fn offset_position(position: usize, offset: isize) -> Option<usize> {
+    /// Returns `Some(u64)` if the offset position is valid, `None` otherwise.
+    /// A position is valid if it does not overflow the `u64` type.
+    pub fn offset_position(position: u64, offset: i64) -> Option<u64> {
         if offset < 0 {
-            position.checked_sub(offset.abs() as usize)
+            position.checked_sub(offset.unsigned_abs())
         } else {
-            position.checked_add(offset as usize)
+            position.checked_add(offset.unsigned_abs())
         }
     }
 
-    /// Compute the future state of the cell
-    pub fn compute_future_state(&self) -> bool {
-        let alive = *self.alive.lock().unwrap();
-        let alive_neighbors = self.count_alive_neighbors();
+    /// Calculates an offset position relative to the cell position and an offset, allowing negative results,
+    /// e.g. for the cells of a pattern before it is translated onto the board.
+    /// Returns `None` if the offset position does not fit in the `i64` type.
+    pub fn offset_position_signed(position: u64, offset: i64) -> Option<i64> {
+        i64::try_from(position).ok()?.checked_add(offset)
+    }
+
+    /// Whether the cell is frozen, see `set_frozen`
+    pub fn is_frozen(&self) -> bool {
+        *lock(&self.frozen)
+    }
+
+    /// Freeze or unfreeze the cell. A frozen cell keeps its state from one generation to the next,
+    /// e.g. for the walls of a contraption, and still counts as a neighbor of the cells around it.
+    pub fn set_frozen(&self, frozen: bool) {
+        *lock(&self.frozen) = frozen;
+    }
+
+    /// Compute the future state of the cell according to the given rule.
+    /// A frozen cell keeps its current state.
+    pub fn compute_future_state(&self, rule: &Rule) -> bool {
+        let alive = self.is_alive();
 
-        match (alive, alive_neighbors) {
-            (true, 2) | (true, 3) => true,
-            (false, 3) => true,
-            _ => false,
+        if self.is_frozen() {
+            return alive;
         }
+
+        let alive_neighbors = self.alive_neighbor_count();
+
+        rule.next_state(alive, alive_neighbors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_position_beyond_u32() {
+        let x = u64::from(u32::MAX) + 10;
+
+        assert_eq!(Cell::offset_position(x, 1), Some(x + 1));
+        assert_eq!(Cell::offset_position(x, -11), Some(u64::from(u32::MAX) - 1));
+    }
+
+    #[test]
+    fn offset_position_near_u64_max() {
+        assert_eq!(Cell::offset_position(u64::MAX - 1, 1), Some(u64::MAX));
+        assert_eq!(Cell::offset_position(u64::MAX, 1), None);
+        assert_eq!(
+            Cell::offset_position(u64::MAX, i64::MIN),
+            Some(u64::MAX - (1 << 63))
+        );
+        assert_eq!(Cell::offset_position(0, i64::MAX), Some(i64::MAX as u64));
+        assert_eq!(Cell::offset_position(0, -1), None);
+    }
+
+    #[test]
+    fn offset_position_signed_across_zero() {
+        assert_eq!(Cell::offset_position_signed(0, -1), Some(-1));
+        assert_eq!(Cell::offset_position_signed(5, -10), Some(-5));
+        assert_eq!(Cell::offset_position_signed(5, -5), Some(0));
+        assert_eq!(Cell::offset_position_signed(0, 1), Some(1));
+
+        // The unsigned version cannot go left of the origin
+        assert_eq!(Cell::offset_position(0, -1), None);
+        assert_eq!(Cell::offset_position(5, -10), None);
+        assert_eq!(Cell::offset_position(5, -5), Some(0));
+    }
+
+    #[test]
+    fn offset_position_signed_near_i64_max() {
+        let max = i64::MAX as u64;
+
+        assert_eq!(Cell::offset_position_signed(max, 0), Some(i64::MAX));
+        assert_eq!(Cell::offset_position_signed(max, 1), None);
+        assert_eq!(Cell::offset_position_signed(max + 1, -1), None);
+        assert_eq!(Cell::offset_position_signed(0, i64::MIN), Some(i64::MIN));
     }
 }
@@ -0,0 +1,210 @@
+use crate::rule::Rule;
+use crate::simulation::{self, MAX_NEIGHBOR_RADIUS};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The default number of past generations kept to compute the activity of the board
+pub const DEFAULT_ACTIVITY_WINDOW: usize = 8;
+
+/// The default number of generations between two snapshots of the board
+pub const DEFAULT_SNAPSHOT_INTERVAL: usize = 10;
+
+/// The default number of generations whose population is kept to plot the evolution of the board
+pub const DEFAULT_POPULATION_HISTORY_LENGTH: usize = 1000;
+
+/// The default neighborhood radius, the eight surrounding cells of Conway's Game of Life
+pub const DEFAULT_NEIGHBOR_RADIUS: usize = 1;
+
+/// An enum representing how the next generation of the board is computed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Backend {
+    /// Count the neighbors through the links between the cells, only around the alive cells
+    #[default]
+    Cells,
+
+    /// Pack the states of the cells in a bitset and sweep every cell, faster on dense boards
+    Bitset,
+
+    /// Delegate to the pure `simulation::step` on the set of the alive positions
+    Simulation,
+}
+
+/// An enum representing what lies beyond the edges of the board
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundaryMode {
+    /// The cells beyond the edges are dead walls
+    #[default]
+    Dead,
+
+    /// The edges wrap around, the left edge touching the right edge and the top edge the bottom edge
+    Toroidal,
+
+    /// The edges are mirrors, a cell beyond an edge being a copy of its reflection on the board,
+    /// so a cell just outside an edge is a copy of the nearest cell on the board
+    Reflective,
+}
+
+/// An enum representing which of the cells within the neighborhood radius are the neighbors of a cell
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NeighborMask {
+    /// The cells within `[-radius, radius]²`, the eight surrounding cells for a radius of 1
+    #[default]
+    Moore,
+
+    /// The cells within a Manhattan distance of `radius`, the four orthogonal cells for a radius of 1
+    VonNeumann,
+
+    /// An explicit list of offsets `(dx, dy)` of the neighbors, each within `[-radius, radius]²`.
+    /// The mask must be symmetric about both axes, e.g. `(1, 2)` comes with `(-1, 2)` and `(1, -2)`,
+    /// so that a cell is a neighbor of its neighbors even through a reflective edge.
+    Custom(Vec<(i8, i8)>),
+}
+
+impl NeighborMask {
+    /// Get the offsets of the neighbors of a cell for the given radius, without the cell itself
+    pub fn offsets(&self, radius: usize) -> Vec<(i64, i64)> {
+        match self {
+            Self::Moore => simulation::neighbor_offsets(radius),
+            Self::VonNeumann => simulation::neighbor_offsets(radius)
+                .into_iter()
+                .filter(|(dx, dy)| dx.unsigned_abs() + dy.unsigned_abs() <= radius as u64)
+                .collect(),
+            Self::Custom(offsets) => offsets
+                .iter()
+                .map(|&(dx, dy)| (i64::from(dx), i64::from(dy)))
+                .collect(),
+        }
+    }
+
+    /// Check that the mask can be used with the given radius.
+    /// The offsets of a custom mask must be within the radius, listed once, exclude the cell itself
+    /// and come with their mirror images about both axes.
+    pub fn validate(&self, radius: usize) -> Result<(), String> {
+        let Self::Custom(offsets) = self else {
+            return Ok(());
+        };
+
+        if offsets.is_empty() {
+            return Err("The neighbor mask must have at least one offset".to_string());
+        }
+
+        let mut seen = HashSet::new();
+
+        for &(dx, dy) in offsets {
+            if (dx, dy) == (0, 0) {
+                return Err("The neighbor mask cannot contain the cell itself".to_string());
+            }
+
+            if usize::from(dx.unsigned_abs()) > radius || usize::from(dy.unsigned_abs()) > radius {
+                return Err(format!(
+                    "The neighbor offset ({dx}, {dy}) is outside of the neighborhood radius {radius}"
+                ));
+            }
+
+            if !seen.insert((dx, dy)) {
+                return Err(format!(
+                    "The neighbor offset ({dx}, {dy}) is listed more than once"
+                ));
+            }
+        }
+
+        // The alive neighbor counts are updated through the links of the cells that change, so they must go both ways
+        for &(dx, dy) in offsets {
+            if let Some((mx, my)) = [(-dx, dy), (dx, -dy)]
+                .into_iter()
+                .find(|mirror| !seen.contains(mirror))
+            {
+                return Err(format!(
+                    "The neighbor mask is not symmetric, the offset ({dx}, {dy}) comes without ({mx}, {my})"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A struct representing the settings of the engine, independently of the cells on the board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineConfig {
+    /// The birth/survival rule of the automaton
+    pub rule: Rule,
+
+    /// What lies beyond the edges of the board
+    pub boundary: BoundaryMode,
+
+    /// The number of past generations kept to compute the activity of the board
+    pub activity_window: usize,
+
+    /// How the next generation of the board is computed
+    pub backend: Backend,
+
+    /// The radius of the neighborhood of a cell, the neighbors being the cells within `[-radius, radius]²`
+    pub neighbor_radius: usize,
+
+    /// Which of the cells within the neighborhood radius are the neighbors of a cell
+    pub neighbor_mask: NeighborMask,
+
+    /// The number of generations between two snapshots of the board, used to restore a past generation
+    pub snapshot_interval: usize,
+
+    /// Whether the ids of the new cells are derived from their position instead of being random
+    pub deterministic_ids: bool,
+
+    /// The number of generations whose population is kept, the oldest being dropped first
+    pub population_history_length: usize,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            rule: Rule::default(),
+            boundary: BoundaryMode::default(),
+            activity_window: DEFAULT_ACTIVITY_WINDOW,
+            backend: Backend::default(),
+            neighbor_radius: DEFAULT_NEIGHBOR_RADIUS,
+            neighbor_mask: NeighborMask::default(),
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+            deterministic_ids: false,
+            population_history_length: DEFAULT_POPULATION_HISTORY_LENGTH,
+        }
+    }
+}
+
+impl EngineConfig {
+    /// Check that every setting of the configuration is valid.
+    /// Returns a description of the first invalid setting found.
+    pub fn validate(&self) -> Result<(), String> {
+        validate_radius(self.neighbor_radius)?;
+
+        self.neighbor_mask.validate(self.neighbor_radius)?;
+
+        self.rule
+            .validate(self.neighbor_mask.offsets(self.neighbor_radius).len())?;
+
+        if self.activity_window == 0 {
+            return Err("The activity window must be at least 1 generation".to_string());
+        }
+
+        if self.snapshot_interval == 0 {
+            return Err("The snapshot interval must be at least 1 generation".to_string());
+        }
+
+        if self.population_history_length == 0 {
+            return Err("The population history must keep at least 1 generation".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that the given neighborhood radius is supported
+pub fn validate_radius(radius: usize) -> Result<(), String> {
+    if (1..=MAX_NEIGHBOR_RADIUS).contains(&radius) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid neighborhood radius {radius}, it must be between 1 and {MAX_NEIGHBOR_RADIUS}"
+        ))
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A struct representing a computed generation of a board, not committed yet.
+/// It is stamped with the generation and the version of the cells it was computed from,
+/// so that a stale diff cannot be committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationDiff {
+    /// The generation of the board when the diff was computed
+    pub generation: usize,
+
+    /// The version of the cells when the diff was computed, bumped by every edit of the cells
+    pub version: usize,
+
+    /// The list of cell ids whose state changes, with their future state
+    pub changes: Vec<(Uuid, bool)>,
+}
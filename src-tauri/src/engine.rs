@@ -0,0 +1,201 @@
+use crate::board::Board;
+use crate::config::{Backend, BoundaryMode, EngineConfig, NeighborMask};
+use crate::error::BoardError;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A struct representing the simulation engine.
+/// It owns a board and drives it without the Tauri runtime, e.g. from a CLI or from tests.
+/// The Tauri commands go through an engine too, reaching the rest of the board features with `board`.
+#[derive(Debug)]
+pub struct Engine {
+    /// The board simulated by the engine, shared with the thread running it on its own, see `Board::start_auto`
+    board: Arc<Board>,
+}
+
+impl Default for Engine {
+    /// Create a new engine with an empty board of size 0x0
+    fn default() -> Self {
+        Self {
+            board: Arc::new(Board::new()),
+        }
+    }
+}
+
+impl Engine {
+    /// Create a new engine with a board of the given width and height, with all cells dead
+    pub fn new(width: u64, height: u64) -> Result<Self, BoardError> {
+        let engine = Self::default();
+
+        engine.board.create_board(width, height)?;
+
+        Ok(engine)
+    }
+
+    /// Get the board simulated by the engine, e.g. for the features beyond the headless API
+    pub fn board(&self) -> Arc<Board> {
+        Arc::clone(&self.board)
+    }
+
+    /// Compute and commit the next generation, see `Board::step`.
+    /// Returns the list of cell ids with their new state, or `None` if the cells are being edited.
+    pub fn step(&self) -> Option<Vec<(Uuid, bool)>> {
+        self.board.step()
+    }
+
+    /// Set the state of the cell at the given position.
+    /// Returns an error if the position is outside of the board.
+    pub fn set_cell(&self, x: u64, y: u64, alive: bool) -> Result<(), BoardError> {
+        let cell = self
+            .board
+            .find_cell(x, y)
+            .ok_or(BoardError::OutOfBounds { x, y })?;
+
+        self.board.update_cells(&[(cell.id, alive)]);
+
+        Ok(())
+    }
+
+    /// Get the positions of the alive cells, sorted by `(x, y)`
+    pub fn alive_cells(&self) -> Vec<(u64, u64)> {
+        self.board.alive_positions()
+    }
+
+    /// Set the birth/survival rule of the engine, see `Board::set_rule`
+    pub fn set_rule(&self, birth: Vec<u8>, survival: Vec<u8>) -> Result<(), BoardError> {
+        self.board.set_rule(birth, survival)
+    }
+
+    /// Set whether the edges of the board wrap around, see `Board::set_wrap`
+    pub fn set_wrap(&self, wrap: bool) {
+        self.board.set_wrap(wrap);
+    }
+
+    /// Set what lies beyond the edges of the board, see `Board::set_boundary_mode`
+    pub fn set_boundary_mode(&self, boundary: BoundaryMode) {
+        self.board.set_boundary_mode(boundary);
+    }
+
+    /// Set the radius of the neighborhood of a cell, see `Board::set_radius`
+    pub fn set_radius(&self, radius: usize) -> Result<(), BoardError> {
+        self.board.set_radius(radius)
+    }
+
+    /// Set which of the cells within the neighborhood radius are the neighbors of a cell, see `Board::set_neighbor_mask`
+    pub fn set_neighbor_mask(&self, mask: NeighborMask) -> Result<(), BoardError> {
+        self.board.set_neighbor_mask(mask)
+    }
+
+    /// Set how the next generation is computed, see `Board::set_backend`
+    pub fn set_backend(&self, backend: Backend) {
+        self.board.set_backend(backend);
+    }
+
+    /// Set whether the ids of the new cells are derived from their position, see `Board::set_deterministic_ids`
+    pub fn set_deterministic_ids(&self, deterministic_ids: bool) {
+        self.board.set_deterministic_ids(deterministic_ids);
+    }
+
+    /// Set the number of generations whose population is kept, see `Board::set_population_history_length`
+    pub fn set_population_history_length(&self, length: usize) -> Result<(), BoardError> {
+        self.board.set_population_history_length(length)
+    }
+
+    /// Get the settings of the engine
+    pub fn config(&self) -> EngineConfig {
+        self.board.export_config()
+    }
+
+    /// Apply new settings to the engine, see `Board::import_config`
+    pub fn set_config(&self, config: EngineConfig) -> Result<(), BoardError> {
+        self.board.import_config(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create an engine of the given size with the cells at the given positions alive
+    fn engine_with(width: u64, height: u64, alive: &[(u64, u64)]) -> Engine {
+        let engine = Engine::new(width, height).unwrap();
+
+        for &(x, y) in alive {
+            engine.set_cell(x, y, true).unwrap();
+        }
+
+        engine
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        let engine = engine_with(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+
+        engine.step().unwrap();
+
+        assert_eq!(engine.alive_cells(), vec![(2, 1), (2, 2), (2, 3)]);
+
+        engine.step().unwrap();
+
+        assert_eq!(engine.alive_cells(), vec![(1, 2), (2, 2), (3, 2)]);
+        assert_eq!(engine.board().get_generation(), 2);
+    }
+
+    #[test]
+    fn set_cell_outside_the_board() {
+        let engine = Engine::new(4, 3).unwrap();
+
+        assert!(matches!(
+            engine.set_cell(4, 0, true),
+            Err(BoardError::OutOfBounds { x: 4, y: 0 })
+        ));
+
+        engine.set_cell(3, 2, true).unwrap();
+        engine.set_cell(3, 2, false).unwrap();
+
+        assert!(engine.alive_cells().is_empty());
+    }
+
+    #[test]
+    fn rule_and_boundary_change_the_next_generation() {
+        // Under B1/S, a lone cell gives birth to its neighbors and dies
+        let engine = engine_with(5, 5, &[(0, 0)]);
+
+        engine.set_rule(vec![1], vec![]).unwrap();
+        engine.set_boundary_mode(BoundaryMode::Toroidal);
+        engine.step().unwrap();
+
+        assert_eq!(engine.board().count_alive(), 8);
+        assert!(engine.alive_cells().contains(&(4, 4)));
+
+        assert!(engine.set_rule(vec![9], vec![]).is_err());
+    }
+
+    #[test]
+    fn config_round_trips_between_engines() {
+        let engine = Engine::new(6, 6).unwrap();
+
+        engine.set_rule(vec![3, 6], vec![2, 3]).unwrap();
+        engine.set_wrap(true);
+        engine.set_backend(Backend::Bitset);
+
+        let copy = Engine::new(6, 6).unwrap();
+
+        copy.set_config(engine.config()).unwrap();
+
+        assert_eq!(copy.config(), engine.config());
+    }
+
+    #[test]
+    fn default_engine_is_empty() {
+        let engine = Engine::default();
+
+        assert_eq!(engine.board().dimensions(), (0, 0));
+        assert!(engine.step().unwrap().is_empty());
+
+        engine.board().create_board(3, 2).unwrap();
+
+        assert_eq!(engine.board().get_cell_positions().len(), 6);
+        assert!(Engine::new(u64::MAX, 2).is_err());
+    }
+}
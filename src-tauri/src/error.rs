@@ -0,0 +1,108 @@
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use uuid::Uuid;
+
+/// An enum representing the errors returned by the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardError {
+    /// A position is outside of the board
+    OutOfBounds { x: u64, y: u64 },
+
+    /// The requested size of the board is invalid, e.g. it has too many cells
+    InvalidDimensions { width: u64, height: u64 },
+
+    /// A setting of the engine is invalid, e.g. a neighbor count of the rule
+    InvalidConfig(String),
+
+    /// A pattern could not be parsed
+    InvalidPattern(String),
+
+    /// A file could not be read, written or parsed
+    File(String),
+
+    /// An image of the board cannot be rendered, e.g. its size overflows
+    InvalidImage(String),
+
+    /// No snapshot of the board was recorded for a generation
+    MissingSnapshot { generation: usize },
+
+    /// The board must be square, e.g. to be rotated
+    NotSquare { width: u64, height: u64 },
+
+    /// No board of the registry has the given id
+    UnknownBoard { id: Uuid },
+
+    /// A recording cannot be replayed, e.g. it is still running or it has fewer steps
+    InvalidReplay(String),
+
+    /// The board cannot step forward to a generation it is already at or past
+    GenerationInPast { target: usize, current: usize },
+
+    /// A diff cannot be committed, as it was computed from another generation than the current one
+    StaleDiff { computed: usize, current: usize },
+
+    /// A diff cannot be committed, as the cells were edited since it was computed
+    EditedSinceDiff,
+
+    /// The board cannot change generation while the user is editing the cells
+    Editing,
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { x, y } => {
+                write!(f, "The position ({x}, {y}) is outside of the board")
+            }
+            Self::InvalidDimensions { width, height } => {
+                write!(f, "Invalid board dimensions {width}x{height}")
+            }
+            Self::InvalidConfig(message) => write!(f, "Invalid configuration: {message}"),
+            Self::InvalidPattern(message) => write!(f, "Invalid pattern: {message}"),
+            Self::File(message) => write!(f, "{message}"),
+            Self::InvalidImage(message) => write!(f, "Invalid image: {message}"),
+            Self::MissingSnapshot { generation } => {
+                write!(
+                    f,
+                    "No snapshot of the board was recorded for generation {generation}"
+                )
+            }
+            Self::NotSquare { width, height } => {
+                write!(f, "The board is {width}x{height}, it must be square")
+            }
+            Self::UnknownBoard { id } => write!(f, "No board has the id {id}"),
+            Self::InvalidReplay(message) => write!(f, "Invalid replay: {message}"),
+            Self::GenerationInPast { target, current } => {
+                write!(
+                    f,
+                    "The board is at generation {current}, it cannot step forward to generation {target}"
+                )
+            }
+            Self::StaleDiff { computed, current } => {
+                write!(
+                    f,
+                    "The diff was computed from generation {computed}, but the board is at generation {current}"
+                )
+            }
+            Self::EditedSinceDiff => {
+                write!(f, "The cells were edited since the diff was computed")
+            }
+            Self::Editing => write!(f, "The cells are being edited"),
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+impl From<BoardError> for String {
+    fn from(error: BoardError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Lock a mutex, recovering its value if a thread panicked while holding it.
+/// The values guarded by the board and the cells are always left consistent between two statements,
+/// so a poisoned lock does not mean corrupted data and the backend can keep going.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
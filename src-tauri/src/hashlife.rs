@@ -0,0 +1,475 @@
+use crate::error::lock;
+use crate::rule::Rule;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The id of a node of the quadtree, an index in the list of nodes of the universe
+type NodeId = usize;
+
+/// The leaf of a dead cell
+const DEAD: NodeId = 0;
+
+/// The leaf of an alive cell
+const ALIVE: NodeId = 1;
+
+/// The lowest level of the root, a square of 8x8 cells
+const MIN_ROOT_LEVEL: usize = 3;
+
+/// The largest distance of an alive cell from the origin, so that the quadtree stays within 64-bit coordinates
+pub const MAX_COORDINATE: i64 = 1 << 60;
+
+/// The largest number of generations the board can be advanced by at once
+pub const MAX_STEPS: u64 = 1 << 58;
+
+/// A struct representing a node of the quadtree, a square of `2^level` cells on each side
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    /// The level of the node, 0 for a single cell
+    level: usize,
+
+    /// The four quadrants of the node, in the order north-west, north-east, south-west, south-east.
+    /// Unused for the leaves.
+    children: [NodeId; 4],
+
+    /// The number of alive cells in the node
+    population: u64,
+}
+
+/// A struct holding the canonical nodes of the quadtree and the memoized results
+#[derive(Debug)]
+struct Universe {
+    /// The rule of the automaton
+    rule: Rule,
+
+    /// The list of nodes, the first two being the dead and alive leaves
+    nodes: Vec<Node>,
+
+    /// The canonical node of every combination of quadrants, so that equal subpatterns are shared
+    canonical: HashMap<[NodeId; 4], NodeId>,
+
+    /// The empty node of every level
+    empty: Vec<NodeId>,
+
+    /// The memoized results of `successor`, keyed by node and by the base 2 logarithm of the steps
+    results: HashMap<(NodeId, usize), NodeId>,
+
+    /// The root of the quadtree, centered on the origin
+    root: NodeId,
+}
+
+impl Universe {
+    /// Create a new empty universe with the given rule
+    fn new(rule: Rule) -> Self {
+        let leaf = |population| Node {
+            level: 0,
+            children: [DEAD; 4],
+            population,
+        };
+
+        let mut universe = Self {
+            rule,
+            nodes: vec![leaf(0), leaf(1)],
+            canonical: HashMap::new(),
+            empty: vec![DEAD],
+            results: HashMap::new(),
+            root: DEAD,
+        };
+
+        universe.root = universe.empty(MIN_ROOT_LEVEL);
+
+        universe
+    }
+
+    /// Get the node with the given id
+    fn node(&self, id: NodeId) -> Node {
+        self.nodes[id]
+    }
+
+    /// Get the canonical node with the given quadrants
+    fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(&id) = self.canonical.get(&children) {
+            return id;
+        }
+
+        let level = self.node(children[0]).level + 1;
+        let population = children
+            .iter()
+            .map(|&child| self.node(child).population)
+            .sum();
+
+        let id = self.nodes.len();
+
+        self.nodes.push(Node {
+            level,
+            children,
+            population,
+        });
+        self.canonical.insert(children, id);
+
+        id
+    }
+
+    /// Get the empty node of the given level
+    fn empty(&mut self, level: usize) -> NodeId {
+        while self.empty.len() <= level {
+            let child = self.empty[self.empty.len() - 1];
+            let empty = self.join([child; 4]);
+
+            self.empty.push(empty);
+        }
+
+        self.empty[level]
+    }
+
+    /// Get the node one level higher with the given node at its center
+    fn expand(&mut self, id: NodeId) -> NodeId {
+        let node = self.node(id);
+        let empty = self.empty(node.level - 1);
+        let [nw, ne, sw, se] = node.children;
+
+        let nw = self.join([empty, empty, empty, nw]);
+        let ne = self.join([empty, empty, ne, empty]);
+        let sw = self.join([empty, sw, empty, empty]);
+        let se = self.join([se, empty, empty, empty]);
+
+        self.join([nw, ne, sw, se])
+    }
+
+    /// Get the node one level lower at the center of the given node
+    fn center(&mut self, id: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.node(id).children;
+
+        self.join([
+            self.node(nw).children[3],
+            self.node(ne).children[2],
+            self.node(sw).children[1],
+            self.node(se).children[0],
+        ])
+    }
+
+    /// Whether the alive cells of the given node are all in its central half, away from its border
+    fn is_centered(&mut self, id: NodeId) -> bool {
+        let population = self.node(id).population;
+        let center = self.center(id);
+
+        self.node(center).population == population
+    }
+
+    /// Get the state of the cell at the given position, relative to the top left corner of the node
+    fn get(&self, id: NodeId, x: u64, y: u64) -> bool {
+        let node = self.node(id);
+
+        if node.level == 0 {
+            return id == ALIVE;
+        }
+
+        let half = 1 << (node.level - 1);
+        let quadrant = usize::from(x >= half) + 2 * usize::from(y >= half);
+
+        self.get(node.children[quadrant], x % half, y % half)
+    }
+
+    /// Set the state of the cell at the given position, relative to the top left corner of the node.
+    /// Returns the node with the cell set.
+    fn set(&mut self, id: NodeId, x: u64, y: u64, alive: bool) -> NodeId {
+        let node = self.node(id);
+
+        if node.level == 0 {
+            return if alive { ALIVE } else { DEAD };
+        }
+
+        let half = 1 << (node.level - 1);
+        let quadrant = usize::from(x >= half) + 2 * usize::from(y >= half);
+
+        let mut children = node.children;
+        children[quadrant] = self.set(children[quadrant], x % half, y % half, alive);
+
+        self.join(children)
+    }
+
+    /// Collect the positions of the alive cells of the node, offset by the position of its top left corner
+    fn collect(&self, id: NodeId, x: i64, y: i64, cells: &mut Vec<(i64, i64)>) {
+        let node = self.node(id);
+
+        if node.population == 0 {
+            return;
+        }
+
+        if node.level == 0 {
+            cells.push((x, y));
+            return;
+        }
+
+        let half = 1 << (node.level - 1);
+
+        for (quadrant, &child) in node.children.iter().enumerate() {
+            let dx = if quadrant % 2 == 1 { half } else { 0 };
+            let dy = if quadrant >= 2 { half } else { 0 };
+
+            self.collect(child, x + dx, y + dy, cells);
+        }
+    }
+
+    /// Compute one generation of the center 2x2 cells of a 4x4 node
+    fn base_successor(&mut self, id: NodeId) -> NodeId {
+        let mut next = [DEAD; 4];
+
+        for (quadrant, state) in next.iter_mut().enumerate() {
+            let x = 1 + (quadrant % 2) as u64;
+            let y = 1 + (quadrant / 2) as u64;
+
+            let alive_neighbors = (-1..=1)
+                .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                .filter(|&offset| offset != (0, 0))
+                .filter(|&(dx, dy)| {
+                    self.get(id, x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
+                })
+                .count();
+
+            if self.rule.next_state(self.get(id, x, y), alive_neighbors) {
+                *state = ALIVE;
+            }
+        }
+
+        self.join(next)
+    }
+
+    /// Compute the center of the given node of level `k`, a node of level `k - 1`,
+    /// after `2^step_log` generations, with `step_log <= k - 2`
+    fn successor(&mut self, id: NodeId, step_log: usize) -> NodeId {
+        let node = self.node(id);
+
+        if node.population == 0 {
+            return self.empty(node.level - 1);
+        }
+
+        if let Some(&result) = self.results.get(&(id, step_log)) {
+            return result;
+        }
+
+        let result = if node.level == 2 {
+            self.base_successor(id)
+        } else {
+            let [nw, ne, sw, se] = node.children.map(|child| self.node(child).children);
+
+            // The nine overlapping nodes of level `k - 1` covering the node
+            let parts = [
+                node.children[0],
+                self.join([nw[1], ne[0], nw[3], ne[2]]),
+                node.children[1],
+                self.join([nw[2], nw[3], sw[0], sw[1]]),
+                self.join([nw[3], ne[2], sw[1], se[0]]),
+                self.join([ne[2], ne[3], se[0], se[1]]),
+                node.children[2],
+                self.join([sw[1], se[0], sw[3], se[2]]),
+                node.children[3],
+            ];
+
+            // At full speed both halves advance the pattern, otherwise only the second one does
+            let full_speed = step_log == node.level - 2;
+
+            let parts = parts.map(|part| {
+                if full_speed {
+                    self.successor(part, step_log - 1)
+                } else {
+                    self.center(part)
+                }
+            });
+
+            let second_step_log = if full_speed { step_log - 1 } else { step_log };
+
+            let quadrants =
+                [[0, 1, 3, 4], [1, 2, 4, 5], [3, 4, 6, 7], [4, 5, 7, 8]].map(|indices| {
+                    let quadrant = self.join(indices.map(|index| parts[index]));
+
+                    self.successor(quadrant, second_step_log)
+                });
+
+            self.join(quadrants)
+        };
+
+        self.results.insert((id, step_log), result);
+
+        result
+    }
+
+    /// Advance the root by `2^step_log` generations
+    fn advance_power_of_two(&mut self, step_log: usize) {
+        while self.node(self.root).level < step_log + 2 || !self.is_centered(self.root) {
+            self.root = self.expand(self.root);
+        }
+
+        // One more level, so that the pattern cannot grow out of the center kept by `successor`
+        self.root = self.expand(self.root);
+
+        self.root = self.successor(self.root, step_log);
+    }
+
+    /// Get the coordinate of the top left corner of the root, which is centered on the origin
+    fn root_corner(&self) -> i64 {
+        -(1 << (self.node(self.root).level - 1))
+    }
+
+    /// Whether the root contains the given position
+    fn root_contains(&self, x: i64, y: i64) -> bool {
+        let corner = self.root_corner();
+
+        (corner..-corner).contains(&x) && (corner..-corner).contains(&y)
+    }
+}
+
+/// A struct representing an unbounded board simulated with the Hashlife algorithm.
+/// The equal subpatterns are stored once in a quadtree and their future is memoized,
+/// so regular patterns can be advanced by a huge number of generations at once.
+#[derive(Debug)]
+pub struct HashlifeBoard {
+    /// The quadtree and the memoized results
+    universe: Mutex<Universe>,
+}
+
+impl HashlifeBoard {
+    /// Create a new board with the given rule and alive cells.
+    /// Returns an error if the rule gives birth to cells with no alive neighbor,
+    /// as an empty region would not stay empty, or if a cell is further than `MAX_COORDINATE` from the origin.
+    pub fn new(rule: Rule, cells: &[(i64, i64)]) -> Result<Self, String> {
+        if rule.birth.contains(&0) {
+            return Err(
+                "Hashlife does not support the rules with birth on 0 neighbors".to_string(),
+            );
+        }
+
+        if let Some(&(x, y)) = cells
+            .iter()
+            .find(|&&(x, y)| x.abs() > MAX_COORDINATE || y.abs() > MAX_COORDINATE)
+        {
+            return Err(format!(
+                "The cell ({x}, {y}) is too far from the origin, the coordinates must be within ±{MAX_COORDINATE}"
+            ));
+        }
+
+        let mut universe = Universe::new(rule);
+
+        for &(x, y) in cells {
+            // Grow the root until it contains the cell
+            while !universe.root_contains(x, y) {
+                universe.root = universe.expand(universe.root);
+            }
+
+            let corner = universe.root_corner();
+
+            universe.root =
+                universe.set(universe.root, x.abs_diff(corner), y.abs_diff(corner), true);
+        }
+
+        Ok(Self {
+            universe: Mutex::new(universe),
+        })
+    }
+
+    /// Advance the board by the given number of generations.
+    /// Returns an error if the number of generations is larger than `MAX_STEPS`.
+    pub fn advance(&self, steps: u64) -> Result<(), String> {
+        if steps > MAX_STEPS {
+            return Err(format!(
+                "Cannot advance by {steps} generations at once, the maximum is {MAX_STEPS}"
+            ));
+        }
+
+        let mut universe = lock(&self.universe);
+
+        for step_log in 0..u64::BITS as usize {
+            if steps & (1 << step_log) != 0 {
+                universe.advance_power_of_two(step_log);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the number of alive cells on the board
+    pub fn population(&self) -> u64 {
+        let universe = lock(&self.universe);
+
+        universe.node(universe.root).population
+    }
+
+    /// Get the positions of the alive cells, sorted by `(x, y)`
+    pub fn cells(&self) -> Vec<(i64, i64)> {
+        let universe = lock(&self.universe);
+
+        let corner = universe.root_corner();
+        let mut cells = Vec::new();
+
+        universe.collect(universe.root, corner, corner, &mut cells);
+
+        cells.sort_unstable();
+
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BoundaryMode;
+    use crate::patterns::Pattern;
+    use crate::simulation;
+    use std::collections::HashSet;
+
+    /// A glider heading towards the bottom right, one cell diagonally every 4 generations
+    const GLIDER: [(i64, i64); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    #[test]
+    fn glider_moves_one_cell_every_four_generations() {
+        for n in [1, 2, 25, 1000, 1 << 20] {
+            let board = HashlifeBoard::new(Rule::default(), &GLIDER).unwrap();
+
+            board.advance(4 * n).unwrap();
+
+            let mut expected: Vec<(i64, i64)> = GLIDER
+                .iter()
+                .map(|&(x, y)| (x + n as i64, y + n as i64))
+                .collect();
+            expected.sort_unstable();
+
+            assert_eq!(board.cells(), expected, "after {} generations", 4 * n);
+        }
+    }
+
+    #[test]
+    fn gun_matches_the_naive_engine() {
+        // Far enough from the edges of the naive board for them not to matter
+        let origin = 1_000;
+        let gun = Pattern::GosperGliderGun.cells();
+
+        let board = HashlifeBoard::new(
+            Rule::default(),
+            &gun.iter()
+                .map(|&(x, y)| (x as i64, y as i64))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+
+        let mut naive: HashSet<(u64, u64)> =
+            gun.iter().map(|&(x, y)| (x + origin, y + origin)).collect();
+
+        for generation in 1..=120 {
+            board.advance(1).unwrap();
+            naive = simulation::step(
+                &naive,
+                2 * origin,
+                2 * origin,
+                BoundaryMode::Dead,
+                &Rule::default(),
+            );
+
+            let mut expected: Vec<(i64, i64)> = naive
+                .iter()
+                .map(|&(x, y)| (x as i64 - origin as i64, y as i64 - origin as i64))
+                .collect();
+            expected.sort_unstable();
+
+            assert_eq!(board.cells(), expected, "generation {generation}");
+        }
+    }
+}
@@ -0,0 +1,19 @@
+pub mod bitgrid;
+pub mod board;
+pub mod cell;
+pub mod config;
+pub mod diff;
+pub mod engine;
+pub mod error;
+pub mod hashlife;
+pub mod life106;
+pub mod parallel;
+pub mod patterns;
+pub mod plaintext;
+pub mod recorder;
+pub mod registry;
+pub mod rle;
+pub mod rule;
+pub mod save;
+pub mod simulation;
+pub mod stats;
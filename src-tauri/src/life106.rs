@@ -0,0 +1,106 @@
+/// The header line of the Life 1.06 format
+const HEADER: &str = "#Life 1.06";
+
+/// Parse a pattern in the Life 1.06 format, a `#Life 1.06` header followed by one `x y` line per alive cell.
+/// The other lines starting with `#` are comments.
+/// Returns the positions of the alive cells, which may be negative.
+pub fn parse_life106(input: &str) -> Result<Vec<(i64, i64)>, String> {
+    let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+
+    if lines.next() != Some(HEADER) {
+        return Err(format!(
+            "The Life 1.06 pattern is missing the `{HEADER}` header"
+        ));
+    }
+
+    lines
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| {
+            let mut coordinates = line.split_whitespace().map(str::parse::<i64>);
+
+            match (coordinates.next(), coordinates.next(), coordinates.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => Ok((x, y)),
+                _ => Err(format!("Invalid line `{line}` in the Life 1.06 pattern")),
+            }
+        })
+        .collect()
+}
+
+/// Translate the cells of a pattern so that its minimum coordinates are at the origin.
+/// The distance between two `i64` coordinates always fits in a `u64`, so no cell is dropped.
+pub fn to_origin(cells: &[(i64, i64)]) -> Vec<(u64, u64)> {
+    let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+
+    cells
+        .iter()
+        .map(|&(x, y)| (x.abs_diff(min_x), y.abs_diff(min_y)))
+        .collect()
+}
+
+/// Write a pattern in the Life 1.06 format, one line per alive cell
+pub fn write_life106(cells: &[(i64, i64)]) -> String {
+    let mut output = format!("{HEADER}\n");
+
+    for (x, y) in cells {
+        output.push_str(&format!("{x} {y}\n"));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_negative_coordinates() {
+        let glider = "#Life 1.06\n#D A glider around the origin\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+
+        let cells = parse_life106(glider).unwrap();
+
+        assert_eq!(cells, vec![(0, -1), (1, 0), (-1, 1), (0, 1), (1, 1)]);
+        assert_eq!(
+            to_origin(&cells),
+            vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn reject_malformed_patterns() {
+        let malformed = [
+            (
+                "",
+                "The Life 1.06 pattern is missing the `#Life 1.06` header",
+            ),
+            (
+                "0 0\n1 1\n",
+                "The Life 1.06 pattern is missing the `#Life 1.06` header",
+            ),
+            (
+                "#Life 1.06\n0 0\n1\n",
+                "Invalid line `1` in the Life 1.06 pattern",
+            ),
+            (
+                "#Life 1.06\n0 0 0\n",
+                "Invalid line `0 0 0` in the Life 1.06 pattern",
+            ),
+            (
+                "#Life 1.06\nx 1\n",
+                "Invalid line `x 1` in the Life 1.06 pattern",
+            ),
+        ];
+
+        for (input, error) in malformed {
+            assert_eq!(parse_life106(input), Err(error.to_string()), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn write_and_parse_round_trip() {
+        let cells = vec![(-3, 7), (0, 0), (i64::MIN, i64::MAX)];
+
+        assert_eq!(parse_life106(&write_life106(&cells)), Ok(cells));
+        assert_eq!(parse_life106(&write_life106(&[])), Ok(Vec::new()));
+    }
+}
@@ -1,25 +1,139 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod board;
-mod cell;
-
-use board::Board;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::sync::Arc;
-use tauri::State;
+use game_of_life::config::{Backend, BoundaryMode, EngineConfig, NeighborMask};
+use game_of_life::diff::GenerationDiff;
+use game_of_life::life106;
+use game_of_life::parallel;
+use game_of_life::patterns::{self, Orientation, Pattern};
+use game_of_life::plaintext;
+use game_of_life::recorder::DEFAULT_MAX_RECORDING_LENGTH;
+use game_of_life::registry::BoardRegistry;
+use game_of_life::rle;
+use game_of_life::rule::Rule;
+use game_of_life::save::BoardSnapshot;
+use game_of_life::stats::BoardStats;
+use std::time::Duration;
+use tauri::{State, Window};
 use uuid::Uuid;
 
+/// The cells that became alive when stamping a pattern, and a warning if the pattern was clipped
+type PlacedPattern = (Vec<(Uuid, bool)>, Option<String>);
+
+/// The id, the position, the state and the age of a cell
+type CellWithAge = (Uuid, u64, u64, bool, usize);
+
+/// The id, the position and the state of a neighbor of a cell
+type Neighbor = (Uuid, u64, u64, bool);
+
+/// The net change of the population after a generation, and the cells that changed with their new state
+type StepDelta = (i64, Vec<(Uuid, bool)>);
+
 fn main() {
-    let board = Arc::new(Board::new());
+    let boards = BoardRegistry::new();
 
     tauri::Builder::default()
-        .manage(board)
+        .manage(boards)
         .invoke_handler(tauri::generate_handler![
             create_board,
             kill_board,
             compute_next_gen,
-            update_cell_state
+            update_cell_state,
+            activity_center,
+            export_config,
+            import_config,
+            step_back_k,
+            pending_births_near,
+            validate_rle,
+            compare_to_pattern,
+            current_generation,
+            population,
+            set_rule,
+            set_wrap,
+            advance,
+            save_board,
+            load_board,
+            load_rle,
+            export_rle,
+            undo,
+            randomize,
+            is_stable,
+            detect_period,
+            resize_board,
+            clear_region,
+            fill_region,
+            cell_at,
+            update_cells,
+            place_pattern,
+            get_cells_with_age,
+            set_backend,
+            toggle_cell,
+            alive_bounds,
+            export_png,
+            start_auto,
+            stop_auto,
+            dispose,
+            set_radius,
+            restore_generation,
+            set_snapshot_interval,
+            neighbors_at,
+            advance_hashlife,
+            import_life106,
+            export_life106,
+            load_cells,
+            mirror,
+            rotate,
+            translate,
+            alive_positions,
+            stats,
+            create_board_with,
+            set_deterministic_ids,
+            dimensions,
+            population_history,
+            set_population_history_length,
+            is_empty,
+            neighbors_of,
+            set_boundary_mode,
+            run_until_stable,
+            configure_threads,
+            set_neighbor_mask,
+            get_cells_page,
+            state_hash,
+            invert,
+            soup_search,
+            cancel_soup_search,
+            snapshot_bitmap,
+            new_board,
+            drop_board,
+            step_full,
+            advance_sampled,
+            step_with_delta,
+            alive_in_region,
+            begin_edit,
+            end_edit,
+            export_snapshot,
+            import_snapshot,
+            start_recording,
+            stop_recording,
+            replay,
+            list_patterns,
+            pattern_exists,
+            step_to_generation,
+            set_matrix,
+            get_matrix,
+            preview_n,
+            classify_next,
+            set_rule_string,
+            find_pattern,
+            step_region,
+            commit_generation,
+            shrink_to_fit,
+            export_stats_csv,
+            freeze_cell,
+            unfreeze_cell,
+            upcoming_births,
+            upcoming_deaths,
+            centroid
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -27,52 +141,1045 @@ fn main() {
 
 #[tauri::command]
 fn create_board(
-    state: State<Arc<Board>>,
-    width: usize,
-    height: usize,
-) -> Vec<(Uuid, usize, usize)> {
-    let board = state.inner();
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    width: u64,
+    height: u64,
+) -> Result<Vec<(Uuid, u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.create_board(width, height)?;
+
+    Ok(board.get_cell_positions())
+}
+
+#[tauri::command]
+fn kill_board(state: State<BoardRegistry>, board_id: Uuid) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.kill_board();
+
+    Ok(())
+}
+
+#[tauri::command]
+fn update_cell_state(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    id: Uuid,
+    new_state: bool,
+) -> Result<(Uuid, bool), String> {
+    let board = state.get(board_id)?.board();
+
+    // The id is returned with whether a cell has it
+    let found = !board.update_cells(&[(id, new_state)]).is_empty();
+
+    Ok((id, found))
+}
+
+#[tauri::command]
+fn compute_next_gen(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+) -> Result<Option<GenerationDiff>, String> {
+    let board = state.get(board_id)?.board();
+
+    // Only computed, the generation counter does not change until the diff is passed to `commit_generation`
+    Ok(board.compute_generation_diff())
+}
+
+#[tauri::command]
+fn activity_center(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+) -> Result<Option<(f64, f64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.activity_center())
+}
+
+#[tauri::command]
+fn export_config(state: State<BoardRegistry>, board_id: Uuid) -> Result<EngineConfig, String> {
+    let engine = state.get(board_id)?;
+
+    Ok(engine.config())
+}
+
+#[tauri::command]
+fn import_config(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    config: EngineConfig,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_config(config).map_err(String::from)
+}
+
+#[tauri::command]
+fn step_back_k(state: State<BoardRegistry>, board_id: Uuid, k: usize) -> Result<bool, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.step_back_k(k))
+}
+
+#[tauri::command]
+fn pending_births_near(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x: u64,
+    y: u64,
+    radius: u64,
+) -> Result<Vec<(u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.pending_births_near(x, y, radius))
+}
+
+#[tauri::command]
+fn validate_rle(rle: String) -> Result<(u64, u64), String> {
+    let (width, height, _) = rle::parse_rle(&rle)?;
+
+    Ok((width, height))
+}
+
+#[tauri::command]
+fn compare_to_pattern(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    rle: String,
+    x: u64,
+    y: u64,
+) -> Result<(usize, usize), String> {
+    let board = state.get(board_id)?.board();
+
+    let (width, height, pattern) = rle::parse_rle(&rle)?;
+
+    Ok(board.compare_to_pattern(width, height, &pattern, x, y))
+}
+
+#[tauri::command]
+fn current_generation(state: State<BoardRegistry>, board_id: Uuid) -> Result<usize, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.get_generation())
+}
+
+#[tauri::command]
+fn population(state: State<BoardRegistry>, board_id: Uuid) -> Result<usize, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.count_alive())
+}
+
+#[tauri::command]
+fn set_rule(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    birth: Vec<u8>,
+    survival: Vec<u8>,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_rule(birth, survival).map_err(String::from)
+}
+
+#[tauri::command]
+fn set_wrap(state: State<BoardRegistry>, board_id: Uuid, wrap: bool) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_wrap(wrap);
+
+    Ok(())
+}
+
+#[tauri::command]
+fn advance(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    n: usize,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.advance(n))
+}
+
+#[tauri::command]
+fn save_board(state: State<BoardRegistry>, board_id: Uuid, path: String) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.save_to_file(&path).map_err(String::from)
+}
+
+#[tauri::command]
+fn load_board(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    path: String,
+) -> Result<Vec<(Uuid, u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.load_from_file(&path).map_err(String::from)
+}
+
+#[tauri::command]
+fn load_rle(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    rle: String,
+    x: u64,
+    y: u64,
+    center: bool,
+) -> Result<PlacedPattern, String> {
+    let board = state.get(board_id)?.board();
+
+    if center {
+        let (_, _, cells) = rle::parse_rle(&rle)?;
+
+        return Ok(board.place_pattern_centered(&cells));
+    }
+
+    let placed = board.load_pattern_rle(&rle, x, y)?;
+
+    Ok((placed, None))
+}
+
+#[tauri::command]
+fn export_rle(state: State<BoardRegistry>, board_id: Uuid) -> Result<String, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.export_rle())
+}
+
+#[tauri::command]
+fn undo(state: State<BoardRegistry>, board_id: Uuid) -> Result<Option<Vec<(Uuid, bool)>>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.undo())
+}
+
+#[tauri::command]
+fn randomize(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    density: f64,
+    seed: Option<u64>,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.randomize(density, seed))
+}
+
+#[tauri::command]
+fn is_stable(state: State<BoardRegistry>, board_id: Uuid) -> Result<bool, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.is_stable())
+}
+
+#[tauri::command]
+fn detect_period(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    max_period: usize,
+) -> Result<Option<usize>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.detect_period(max_period))
+}
+
+#[tauri::command]
+fn resize_board(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    width: u64,
+    height: u64,
+) -> Result<Vec<(Uuid, u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.resize(width, height).map_err(String::from)
+}
+
+#[tauri::command]
+fn clear_region(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x0: u64,
+    y0: u64,
+    x1: u64,
+    y1: u64,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.clear_region(x0, y0, x1, y1))
+}
+
+#[tauri::command]
+fn fill_region(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x0: u64,
+    y0: u64,
+    x1: u64,
+    y1: u64,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.fill_region(x0, y0, x1, y1))
+}
+
+#[tauri::command]
+fn cell_at(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x: u64,
+    y: u64,
+) -> Result<Option<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.find_cell(x, y).map(|cell| (cell.id, cell.is_alive())))
+}
+
+#[tauri::command]
+fn update_cells(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    updates: Vec<(Uuid, bool)>,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.update_cells(&updates))
+}
+
+#[tauri::command]
+fn place_pattern(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    name: String,
+    x: u64,
+    y: u64,
+    center: bool,
+    orientation: Orientation,
+) -> Result<PlacedPattern, String> {
+    let board = state.get(board_id)?.board();
+
+    let pattern = name.parse::<Pattern>()?;
+    let cells = patterns::orient(&pattern.cells(), orientation)?;
+
+    if center {
+        return Ok(board.place_pattern_centered(&cells));
+    }
+
+    Ok((board.place_cells(&cells, x, y), None))
+}
+
+#[tauri::command]
+fn get_cells_with_age(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+) -> Result<Vec<CellWithAge>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.get_cells_with_age())
+}
+
+#[tauri::command]
+fn set_backend(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    backend: Backend,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_backend(backend);
+
+    Ok(())
+}
+
+#[tauri::command]
+fn toggle_cell(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x: u64,
+    y: u64,
+) -> Result<Option<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.toggle_at(x, y))
+}
+
+#[tauri::command]
+fn alive_bounds(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+) -> Result<Option<(u64, u64, u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.alive_bounds())
+}
+
+#[tauri::command]
+fn export_png(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    path: String,
+    cell_px: u64,
+) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.render_png(&path, cell_px).map_err(String::from)
+}
+
+#[tauri::command]
+fn start_auto(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    window: Window,
+    interval_ms: u64,
+) -> Result<bool, String> {
+    let board = state.get(board_id)?.board();
+
+    // The run stops by itself once the window is gone and the event cannot be emitted anymore
+    Ok(
+        board.start_auto(Duration::from_millis(interval_ms), move |changes| {
+            window.emit("generation", changes).is_ok()
+        }),
+    )
+}
+
+#[tauri::command]
+fn stop_auto(state: State<BoardRegistry>, board_id: Uuid) -> Result<bool, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.stop_auto())
+}
+
+#[tauri::command]
+fn dispose(state: State<BoardRegistry>, board_id: Uuid) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.dispose();
+
+    Ok(())
+}
+
+#[tauri::command]
+fn set_radius(state: State<BoardRegistry>, board_id: Uuid, radius: usize) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_radius(radius).map_err(String::from)
+}
+
+#[tauri::command]
+fn restore_generation(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    generation: usize,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.restore_generation(generation).map_err(String::from)
+}
+
+#[tauri::command]
+fn set_snapshot_interval(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    interval: usize,
+) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.set_snapshot_interval(interval).map_err(String::from)
+}
+
+#[tauri::command]
+fn neighbors_at(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x: u64,
+    y: u64,
+) -> Result<Option<usize>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.alive_neighbors_at(x, y))
+}
+
+#[tauri::command]
+fn advance_hashlife(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    steps: u64,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.advance_hashlife(steps).map_err(String::from)
+}
+
+#[tauri::command]
+fn import_life106(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    text: String,
+    x: u64,
+    y: u64,
+    center: bool,
+) -> Result<PlacedPattern, String> {
+    let board = state.get(board_id)?.board();
+
+    if center {
+        let cells = life106::parse_life106(&text)?;
+
+        return Ok(board.place_pattern_centered(&life106::to_origin(&cells)));
+    }
+
+    let placed = board.load_pattern_life106(&text, x, y)?;
+
+    Ok((placed, None))
+}
+
+#[tauri::command]
+fn export_life106(state: State<BoardRegistry>, board_id: Uuid) -> Result<String, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.export_life106())
+}
+
+#[tauri::command]
+fn load_cells(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    text: String,
+    x: u64,
+    y: u64,
+    center: bool,
+) -> Result<PlacedPattern, String> {
+    let board = state.get(board_id)?.board();
+
+    if center {
+        let (_, _, cells) = plaintext::parse_cells(&text)?;
+
+        return Ok(board.place_pattern_centered(&cells));
+    }
 
-    board.create_board(width, height);
+    let placed = board.load_pattern_cells(&text, x, y)?;
+
+    Ok((placed, None))
+}
+
+#[tauri::command]
+fn mirror(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    horizontal: bool,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.mirror(horizontal))
+}
+
+#[tauri::command]
+fn rotate(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    clockwise: bool,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.rotate(clockwise).map_err(String::from)
+}
+
+#[tauri::command]
+fn translate(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    dx: i64,
+    dy: i64,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.translate(dx, dy))
+}
+
+#[tauri::command]
+fn alive_positions(state: State<BoardRegistry>, board_id: Uuid) -> Result<Vec<(u64, u64)>, String> {
+    let engine = state.get(board_id)?;
+
+    Ok(engine.alive_cells())
+}
+
+#[tauri::command]
+fn stats(state: State<BoardRegistry>, board_id: Uuid) -> Result<BoardStats, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.stats())
+}
+
+#[tauri::command]
+fn create_board_with(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    width: u64,
+    height: u64,
+    alive: Vec<(u64, u64)>,
+) -> Result<Vec<(Uuid, u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board
+        .create_with_cells(width, height, &alive)
+        .map_err(String::from)
+}
+
+#[tauri::command]
+fn set_deterministic_ids(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    deterministic_ids: bool,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_deterministic_ids(deterministic_ids);
+
+    Ok(())
+}
+
+#[tauri::command]
+fn dimensions(state: State<BoardRegistry>, board_id: Uuid) -> Result<(u64, u64), String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.dimensions())
+}
+
+#[tauri::command]
+fn population_history(state: State<BoardRegistry>, board_id: Uuid) -> Result<Vec<usize>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.population_history())
+}
+
+#[tauri::command]
+fn set_population_history_length(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    length: usize,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine
+        .set_population_history_length(length)
+        .map_err(String::from)
+}
+
+#[tauri::command]
+fn is_empty(state: State<BoardRegistry>, board_id: Uuid) -> Result<bool, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.is_empty())
+}
+
+#[tauri::command]
+fn neighbors_of(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    id: Uuid,
+) -> Result<Option<Vec<Neighbor>>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.neighbors_of(id))
+}
+
+#[tauri::command]
+fn set_boundary_mode(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    boundary: BoundaryMode,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_boundary_mode(boundary);
+
+    Ok(())
+}
+
+#[tauri::command]
+fn run_until_stable(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    max_generations: usize,
+) -> Result<(usize, bool), String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.run_until_stable(max_generations))
+}
+
+#[tauri::command]
+fn configure_threads(num: usize) -> Result<(), String> {
+    parallel::configure_threads(num)
+}
+
+#[tauri::command]
+fn set_neighbor_mask(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    mask: NeighborMask,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    engine.set_neighbor_mask(mask).map_err(String::from)
+}
+
+#[tauri::command]
+fn get_cells_page(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<(Uuid, u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.get_cells_page(offset, limit))
+}
+
+#[tauri::command]
+fn state_hash(state: State<BoardRegistry>, board_id: Uuid) -> Result<u64, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.state_hash())
+}
+
+#[tauri::command]
+fn invert(state: State<BoardRegistry>, board_id: Uuid) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.invert())
+}
+
+#[tauri::command]
+async fn soup_search(
+    state: State<'_, BoardRegistry>,
+    board_id: Uuid,
+    width: u64,
+    height: u64,
+    samples: usize,
+    min_final_population: usize,
+) -> Result<Option<u64>, String> {
+    let board = state.get(board_id)?.board();
+
+    // The command is async so that the search runs off the main thread, which stays free to cancel it
+    board
+        .soup_search(width, height, samples, min_final_population)
+        .map_err(String::from)
+}
+
+#[tauri::command]
+fn cancel_soup_search(state: State<BoardRegistry>, board_id: Uuid) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.cancel_soup_search();
+
+    Ok(())
+}
+
+#[tauri::command]
+fn snapshot_bitmap(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+) -> Result<(u64, u64, Vec<u8>), String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.snapshot_bitmap())
+}
+
+#[tauri::command]
+fn new_board(state: State<BoardRegistry>) -> Uuid {
+    state.create()
+}
+
+#[tauri::command]
+fn drop_board(state: State<BoardRegistry>, board_id: Uuid) -> bool {
+    state.remove(board_id)
+}
+
+#[tauri::command]
+fn step_full(state: State<BoardRegistry>, board_id: Uuid) -> Result<Vec<(u64, u64)>, String> {
+    let engine = state.get(board_id)?;
+
+    // Skipped while the cells are edited, the current alive set is returned then
+    engine.step();
+
+    // The whole alive set rather than the diff, for the clients drawing the whole board each frame
+    Ok(engine.alive_cells())
+}
+
+#[tauri::command]
+fn advance_sampled(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    n: usize,
+    sample_every: usize,
+) -> Result<Vec<Vec<(u64, u64)>>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.advance_sampled(n, sample_every).map_err(String::from)
+}
+
+#[tauri::command]
+fn step_with_delta(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+) -> Result<Option<StepDelta>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.step_with_delta())
+}
+
+#[tauri::command]
+fn alive_in_region(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x0: u64,
+    y0: u64,
+    x1: u64,
+    y1: u64,
+) -> Result<Vec<(u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.alive_in_region(x0, y0, x1, y1))
+}
+
+#[tauri::command]
+fn begin_edit(state: State<BoardRegistry>, board_id: Uuid) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.begin_edit();
+
+    Ok(())
+}
+
+#[tauri::command]
+fn end_edit(state: State<BoardRegistry>, board_id: Uuid) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
+
+    board.end_edit();
+
+    Ok(())
+}
+
+#[tauri::command]
+fn export_snapshot(state: State<BoardRegistry>, board_id: Uuid) -> Result<String, String> {
+    let board = state.get(board_id)?.board();
+
+    serde_json::to_string(&board.to_snapshot())
+        .map_err(|error| format!("Failed to serialize the board: {error}"))
+}
+
+#[tauri::command]
+fn import_snapshot(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    json: String,
+) -> Result<Vec<(Uuid, u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    let snapshot: BoardSnapshot =
+        serde_json::from_str(&json).map_err(|error| format!("Invalid board snapshot: {error}"))?;
+
+    board.from_snapshot(snapshot).map_err(String::from)
+}
+
+#[tauri::command]
+fn start_recording(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    max_length: Option<usize>,
+) -> Result<(), String> {
+    let board = state.get(board_id)?.board();
 
     board
-        .get_cells()
-        .par_iter()
-        .map(|cell| (cell.id, cell.x, cell.y))
+        .start_recording(max_length.unwrap_or(DEFAULT_MAX_RECORDING_LENGTH))
+        .map_err(String::from)
+}
+
+#[tauri::command]
+fn stop_recording(state: State<BoardRegistry>, board_id: Uuid) -> Result<usize, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.stop_recording())
+}
+
+#[tauri::command]
+fn replay(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    index: usize,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.replay_step(index).map_err(String::from)
+}
+
+#[tauri::command]
+fn list_patterns() -> Vec<String> {
+    patterns::list_names()
+        .into_iter()
+        .map(String::from)
         .collect()
 }
 
 #[tauri::command]
-fn kill_board(state: State<Arc<Board>>) {
-    let board = state.inner();
+fn pattern_exists(name: String) -> bool {
+    patterns::pattern_exists(&name)
+}
 
-    board.kill_board();
+#[tauri::command]
+fn step_to_generation(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    target: usize,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.step_to_generation(target).map_err(String::from)
+}
+
+#[tauri::command]
+fn set_matrix(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    rows: Vec<Vec<bool>>,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.set_from_matrix(rows).map_err(String::from)
+}
+
+#[tauri::command]
+fn get_matrix(state: State<BoardRegistry>, board_id: Uuid) -> Result<Vec<Vec<bool>>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.to_matrix())
+}
+
+#[tauri::command]
+fn preview_n(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    n: usize,
+) -> Result<Vec<Vec<(Uuid, bool)>>, String> {
+    let board = state.get(board_id)?.board();
+
+    board.preview_n(n).map_err(String::from)
+}
+
+#[tauri::command]
+fn classify_next(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+) -> Result<(usize, usize, usize), String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.classify_next())
+}
+
+#[tauri::command]
+fn set_rule_string(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    rule: String,
+) -> Result<(), String> {
+    let engine = state.get(board_id)?;
+
+    let rule = Rule::parse(&rule)?;
+
+    engine
+        .set_rule(rule.birth, rule.survival)
+        .map_err(String::from)
+}
+
+#[tauri::command]
+fn find_pattern(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    pattern: Vec<(u64, u64)>,
+) -> Result<Vec<(u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.find_pattern(pattern))
 }
 
 #[tauri::command]
-fn update_cell_state(state: State<Arc<Board>>, id: Uuid, new_state: bool) -> (Uuid, bool) {
-    let board = state.inner();
+fn step_region(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    x0: u64,
+    y0: u64,
+    x1: u64,
+    y1: u64,
+) -> Result<Vec<(Uuid, bool)>, String> {
+    let board = state.get(board_id)?.board();
 
-    let cell = board.get_cell(id);
+    Ok(board.step_region(x0, y0, x1, y1))
+}
 
-    let result = match cell {
-        Some(cell) => {
-            cell.set_alive(new_state);
-            (cell.id, true)
-        }
-        None => (id, false),
-    };
+#[tauri::command]
+fn commit_generation(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    diff: GenerationDiff,
+) -> Result<i64, String> {
+    let board = state.get(board_id)?.board();
 
-    result
+    board.commit_generation(&diff).map_err(String::from)
 }
 
 #[tauri::command]
-fn compute_next_gen(state: State<Arc<Board>>) -> Vec<(Uuid, bool)> {
-    let board = state.inner();
+fn shrink_to_fit(
+    state: State<BoardRegistry>,
+    board_id: Uuid,
+    margin: u64,
+) -> Result<(u64, u64), String> {
+    let board = state.get(board_id)?.board();
 
-    let next_gen = board.compute_next_generation();
+    board.shrink_to_fit(margin).map_err(String::from)
+}
 
-    board.update_next_generation(&next_gen);
+#[tauri::command]
+fn export_stats_csv(state: State<BoardRegistry>, board_id: Uuid) -> Result<String, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.export_stats_csv())
+}
+
+#[tauri::command]
+fn freeze_cell(state: State<BoardRegistry>, board_id: Uuid, id: Uuid) -> Result<bool, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.set_frozen(id, true))
+}
+
+#[tauri::command]
+fn unfreeze_cell(state: State<BoardRegistry>, board_id: Uuid, id: Uuid) -> Result<bool, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.set_frozen(id, false))
+}
+
+#[tauri::command]
+fn upcoming_births(state: State<BoardRegistry>, board_id: Uuid) -> Result<Vec<(u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.upcoming_births())
+}
+
+#[tauri::command]
+fn upcoming_deaths(state: State<BoardRegistry>, board_id: Uuid) -> Result<Vec<(u64, u64)>, String> {
+    let board = state.get(board_id)?.board();
+
+    Ok(board.upcoming_deaths())
+}
+
+#[tauri::command]
+fn centroid(state: State<BoardRegistry>, board_id: Uuid) -> Result<Option<(f64, f64)>, String> {
+    let board = state.get(board_id)?.board();
 
-    next_gen
+    Ok(board.centroid())
 }
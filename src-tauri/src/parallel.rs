@@ -0,0 +1,43 @@
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::OnceLock;
+
+/// The number of cells under which a board is computed on a single thread.
+/// Below it, splitting the work between the threads costs more than the work itself.
+/// The crossover is measured by the `parallel` benchmark, to run again when tuning it for other hardware.
+///
+/// Measured on a single CPU Xeon, one generation of a random soup, serial and parallel pools, release build:
+/// 1024 cells 0.49/0.49 ms, 4096 cells 1.8/2.5 ms, 16384 cells 7.9/8.0 ms, 65536 cells 37/35 ms,
+/// 262144 cells 210/203 ms. With a single thread the two pools do the same work, so there is no crossover there:
+/// the value is not measured on a multi-core machine yet and is kept until it is.
+pub const SERIAL_THRESHOLD: usize = 16_384;
+
+/// The pool with a single thread running the work of the small boards, `None` if it could not be started
+static SERIAL_POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+
+/// Set the number of threads used to compute the boards, 0 for one thread per CPU.
+/// Returns an error if the threads were already started, i.e. if it is not called at startup before any computation.
+pub fn configure_threads(num: usize) -> Result<(), String> {
+    ThreadPoolBuilder::new()
+        .num_threads(num)
+        .build_global()
+        .map_err(|error| format!("Cannot configure the threads: {error}"))
+}
+
+/// Run the given work for a board of the given number of cells.
+/// The parallel iterators of the work run on a single thread if the board is smaller than `SERIAL_THRESHOLD`,
+/// and on all the threads otherwise.
+/// The work already running on one of the threads, e.g. for one of many boards computed in parallel,
+/// is run as is, so that the boards do not queue up on the single thread.
+/// If the single thread cannot be started, the work is run as is too.
+pub fn run<R: Send>(cells: usize, op: impl FnOnce() -> R + Send) -> R {
+    if cells >= SERIAL_THRESHOLD || rayon::current_thread_index().is_some() {
+        return op();
+    }
+
+    let pool = SERIAL_POOL.get_or_init(|| ThreadPoolBuilder::new().num_threads(1).build().ok());
+
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
@@ -0,0 +1,217 @@
+use crate::rle;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// An enum representing the built-in patterns that can be stamped onto a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// The smallest spaceship, moving diagonally
+    Glider,
+
+    /// The smallest oscillator, of period 2
+    Blinker,
+
+    /// The smallest still life
+    Block,
+
+    /// An oscillator of period 3
+    Pulsar,
+
+    /// A gun emitting a glider every 30 generations
+    GosperGliderGun,
+}
+
+impl Pattern {
+    /// Every built-in pattern, in the order they are listed to the user
+    pub const ALL: [Pattern; 5] = [
+        Pattern::Glider,
+        Pattern::Blinker,
+        Pattern::Block,
+        Pattern::Pulsar,
+        Pattern::GosperGliderGun,
+    ];
+
+    /// Get the name of the pattern, as parsed by `from_str`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pattern::Glider => "glider",
+            Pattern::Blinker => "blinker",
+            Pattern::Block => "block",
+            Pattern::Pulsar => "pulsar",
+            Pattern::GosperGliderGun => "gosper_glider_gun",
+        }
+    }
+
+    /// Get the pattern in the RLE format
+    fn rle(&self) -> &'static str {
+        match self {
+            Pattern::Glider => "x = 3, y = 3\nbo$2bo$3o!",
+            Pattern::Blinker => "x = 3, y = 1\n3o!",
+            Pattern::Block => "x = 2, y = 2\n2o$2o!",
+            Pattern::Pulsar => {
+                "x = 13, y = 13\n\
+                 2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$\
+                 o4bobo4bo$o4bobo4bo2$2b3o3b3o!"
+            }
+            Pattern::GosperGliderGun => {
+                "x = 36, y = 9\n\
+                 24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$\
+                 10bo5bo7bo$11bo3bo$12b2o!"
+            }
+        }
+    }
+
+    /// Get the positions of the alive cells of the pattern, relative to its top left corner
+    pub fn cells(&self) -> Vec<(u64, u64)> {
+        let (_, _, cells) = rle::parse_rle(self.rle()).expect("built-in patterns are valid RLE");
+
+        cells
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    /// Parse a pattern from its name, e.g. `glider` or `gosper_glider_gun`
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Pattern::ALL
+            .into_iter()
+            .find(|pattern| pattern.name() == name)
+            .ok_or_else(|| format!("Unknown pattern `{name}`"))
+    }
+}
+
+/// Get the names of the built-in patterns, as accepted by `Pattern::from_str`
+pub fn list_names() -> Vec<&'static str> {
+    Pattern::ALL.iter().map(Pattern::name).collect()
+}
+
+/// Whether a built-in pattern has the given name
+pub fn pattern_exists(name: &str) -> bool {
+    name.parse::<Pattern>().is_ok()
+}
+
+/// A struct representing how a pattern is oriented before it is stamped onto a board.
+/// The pattern is flipped first, then rotated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Orientation {
+    /// The clockwise rotation of the pattern in degrees, 0, 90, 180 or 270, as `Board::rotate` turns a board
+    pub rotation: u16,
+
+    /// Whether the columns of the pattern are mirrored, its left edge becoming its right edge
+    pub flip_x: bool,
+
+    /// Whether the rows of the pattern are mirrored, its top edge becoming its bottom edge
+    pub flip_y: bool,
+}
+
+/// Orient the cells of a pattern, relative to its top left corner, about its bounding box.
+/// Returns the oriented cells, relative to the top left corner of the oriented pattern,
+/// or an error if the rotation is not 0, 90, 180 or 270 degrees.
+pub fn orient(cells: &[(u64, u64)], orientation: Orientation) -> Result<Vec<(u64, u64)>, String> {
+    let Orientation {
+        rotation,
+        flip_x,
+        flip_y,
+    } = orientation;
+
+    if ![0, 90, 180, 270].contains(&rotation) {
+        return Err(format!(
+            "Invalid rotation of {rotation} degrees, it must be 0, 90, 180 or 270"
+        ));
+    }
+
+    let width = cells.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+    let height = cells.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+
+    Ok(cells
+        .iter()
+        .map(|&(x, y)| {
+            let x = if flip_x { width - 1 - x } else { x };
+            let y = if flip_y { height - 1 - y } else { y };
+
+            match rotation {
+                90 => (height - 1 - y, x),
+                180 => (width - 1 - x, height - 1 - y),
+                270 => (y, width - 1 - x),
+                _ => (x, y),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The glider heading down and right, relative to its top left corner
+    const GLIDER: [(u64, u64); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    /// Orient the cells and sort the result, so that it can be compared with a literal
+    fn oriented(
+        cells: &[(u64, u64)],
+        rotation: u16,
+        flip_x: bool,
+        flip_y: bool,
+    ) -> Vec<(u64, u64)> {
+        let mut cells = orient(
+            cells,
+            Orientation {
+                rotation,
+                flip_x,
+                flip_y,
+            },
+        )
+        .unwrap();
+
+        cells.sort_unstable();
+
+        cells
+    }
+
+    #[test]
+    fn orient_a_glider() {
+        assert_eq!(
+            oriented(&GLIDER, 90, false, false),
+            vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 1)]
+        );
+
+        // Flipped first, then rotated
+        assert_eq!(
+            oriented(&GLIDER, 90, true, false),
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (2, 1)]
+        );
+
+        assert_eq!(
+            oriented(&GLIDER, 180, true, true),
+            oriented(&GLIDER, 0, false, false)
+        );
+    }
+
+    #[test]
+    fn orient_a_rectangle() {
+        // An L three cells wide and two cells tall becomes two cells wide and three cells tall
+        let l = [(0, 0), (0, 1), (1, 1), (2, 1)];
+
+        assert_eq!(
+            oriented(&l, 90, false, false),
+            vec![(0, 0), (0, 1), (0, 2), (1, 0)]
+        );
+        assert_eq!(
+            oriented(&l, 270, false, false),
+            vec![(0, 2), (1, 0), (1, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn orient_rejects_other_rotations() {
+        assert!(orient(
+            &GLIDER,
+            Orientation {
+                rotation: 45,
+                ..Orientation::default()
+            }
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::rle::ParsedPattern;
+use std::collections::HashSet;
+
+/// Parse a pattern in the plaintext format (`.cells`), with `.` for a dead cell and `O` for an alive one.
+/// The lines starting with `!` are comments, and the whitespace around each line is ignored.
+/// The lines may have different lengths, the missing cells at the end of a line are dead.
+/// Returns the width and height of the pattern and the positions of the alive cells,
+/// relative to the top left corner of the pattern.
+pub fn parse_cells(input: &str) -> Result<ParsedPattern, String> {
+    let mut rows: Vec<&str> = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('!'))
+        .skip_while(|line| line.is_empty())
+        .collect();
+
+    // The empty lines at the end are not part of the pattern
+    while rows.last().is_some_and(|line| line.is_empty()) {
+        rows.pop();
+    }
+
+    let mut width = 0;
+    let mut cells = Vec::new();
+
+    for (y, row) in (0_u64..).zip(&rows) {
+        for (x, token) in (0_u64..).zip(row.chars()) {
+            match token {
+                'O' => cells.push((x, y)),
+                '.' => {}
+                token => {
+                    return Err(format!(
+                        "Unexpected character `{token}` on row {} of the plaintext pattern",
+                        y + 1
+                    ));
+                }
+            }
+
+            width = width.max(x + 1);
+        }
+    }
+
+    Ok((width, rows.len() as u64, cells))
+}
+
+/// Write a pattern in the plaintext format (`.cells`), one line of `.` and `O` per row.
+/// The positions of the alive cells are relative to the top left corner of the pattern,
+/// and the ones outside of the given width and height are ignored.
+pub fn write_cells(width: u64, height: u64, cells: &[(u64, u64)]) -> String {
+    let alive: HashSet<(u64, u64)> = cells.iter().copied().collect();
+
+    let mut output = String::new();
+
+    for y in 0..height {
+        output.extend((0..width).map(|x| if alive.contains(&(x, y)) { 'O' } else { '.' }));
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The glider, relative to its top left corner
+    const GLIDER: [(u64, u64); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    #[test]
+    fn parse_ragged_glider() {
+        // The trailing dead cells are left out, as many files do
+        let glider = "!Name: Glider\n.O\n..O\nOOO";
+
+        assert_eq!(parse_cells(glider), Ok((3, 3, GLIDER.to_vec())));
+    }
+
+    #[test]
+    fn parse_around_blank_lines_and_whitespace() {
+        let glider = "\n\n!Name: Glider\n  .O.  \n\t..O\n!A comment between the rows\nOOO \r\n\n\n";
+
+        assert_eq!(parse_cells(glider), Ok((3, 3, GLIDER.to_vec())));
+
+        // An empty line inside the pattern is a row of dead cells
+        assert_eq!(parse_cells("O\n\nO"), Ok((1, 3, vec![(0, 0), (0, 2)])));
+
+        assert_eq!(parse_cells("!Only a comment\n\n"), Ok((0, 0, Vec::new())));
+    }
+
+    #[test]
+    fn reject_unexpected_characters() {
+        assert_eq!(
+            parse_cells(".O\n..X\nOOO"),
+            Err("Unexpected character `X` on row 2 of the plaintext pattern".to_string())
+        );
+
+        // The whitespace is only trimmed around the lines
+        assert_eq!(
+            parse_cells("O O"),
+            Err("Unexpected character ` ` on row 1 of the plaintext pattern".to_string())
+        );
+    }
+
+    #[test]
+    fn write_and_parse_round_trip() {
+        let text = write_cells(4, 3, &GLIDER);
+
+        assert_eq!(text, ".O..\n..O.\nOOO.\n");
+        assert_eq!(parse_cells(&text), Ok((4, 3, GLIDER.to_vec())));
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// The number of generations kept by a recording when no maximum is given
+pub const DEFAULT_MAX_RECORDING_LENGTH: usize = 10_000;
+
+/// A struct recording the changes of a board generation after generation, so that they can be replayed.
+/// Unlike the undo history, the recording is a forward timeline starting from the states of the cells
+/// when it was started, and any of its steps can be replayed.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    /// Whether the generations are being recorded
+    recording: bool,
+
+    /// The number of generations kept, the oldest being folded into `initial_states` first
+    max_length: usize,
+
+    /// The generation of the board before the first recorded step
+    start_generation: usize,
+
+    /// The states of the cells before the first recorded step
+    initial_states: HashMap<Uuid, bool>,
+
+    /// The cell ids with their new state for each recorded generation, the oldest first
+    steps: VecDeque<Vec<(Uuid, bool)>>,
+}
+
+impl Recorder {
+    /// Start a new recording from the given states of the cells, forgetting the previous one
+    pub fn start(
+        &mut self,
+        initial_states: HashMap<Uuid, bool>,
+        generation: usize,
+        max_length: usize,
+    ) {
+        self.recording = true;
+        self.max_length = max_length;
+        self.start_generation = generation;
+        self.initial_states = initial_states;
+        self.steps.clear();
+    }
+
+    /// Stop recording, the recorded steps are kept to be replayed
+    pub fn stop(&mut self) {
+        self.recording = false;
+    }
+
+    /// Whether the generations are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Record the changes of a generation if recording.
+    /// Beyond the maximum length, the oldest step is applied to the initial states and dropped.
+    pub fn record(&mut self, changes: &[(Uuid, bool)]) {
+        if !self.recording {
+            return;
+        }
+
+        self.steps.push_back(changes.to_vec());
+
+        while self.steps.len() > self.max_length {
+            if let Some(oldest) = self.steps.pop_front() {
+                self.initial_states.extend(oldest);
+                self.start_generation += 1;
+            }
+        }
+    }
+
+    /// Get the number of recorded steps
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Whether no step was recorded
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Get the generation of the board after the given number of recorded steps
+    pub fn generation_at(&self, index: usize) -> usize {
+        self.start_generation + index
+    }
+
+    /// Get the recorded steps, the oldest first
+    pub fn steps(&self) -> Vec<Vec<(Uuid, bool)>> {
+        self.steps.iter().cloned().collect()
+    }
+
+    /// Get the states of the cells after the given number of recorded steps, 0 being the start of the recording.
+    /// Returns `None` if fewer steps were recorded.
+    pub fn states_at(&self, index: usize) -> Option<Vec<(Uuid, bool)>> {
+        if index > self.steps.len() {
+            return None;
+        }
+
+        let mut states = self.initial_states.clone();
+
+        for step in self.steps.iter().take(index) {
+            states.extend(step.iter().copied());
+        }
+
+        Some(states.into_iter().collect())
+    }
+
+    /// Forget the steps recorded after the given generation, e.g. when the board stepped back to it.
+    /// If the board went back before the start of the recording, the whole recording is forgotten.
+    pub fn truncate(&mut self, generation: usize) {
+        match generation.checked_sub(self.start_generation) {
+            Some(length) => self.steps.truncate(length),
+            None => self.clear(),
+        }
+    }
+
+    /// Forget the recording and stop recording
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
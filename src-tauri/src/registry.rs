@@ -0,0 +1,51 @@
+use crate::engine::Engine;
+use crate::error::BoardError;
+use dashmap::DashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A struct holding several independent engines keyed by the id of their board, e.g. to run simulations side by side
+#[derive(Debug, Default)]
+pub struct BoardRegistry {
+    /// The engines of the registry, each driving its own board
+    boards: DashMap<Uuid, Arc<Engine>>,
+}
+
+impl BoardRegistry {
+    /// Create a new registry with no board
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a new empty board to the registry.
+    /// Returns the id of the board.
+    pub fn create(&self) -> Uuid {
+        let id = Uuid::new_v4();
+
+        self.boards.insert(id, Arc::new(Engine::default()));
+
+        id
+    }
+
+    /// Get the engine of the board with the given id.
+    /// Returns an error if no board has that id.
+    pub fn get(&self, id: Uuid) -> Result<Arc<Engine>, BoardError> {
+        self.boards
+            .get(&id)
+            .map(|board| Arc::clone(board.value()))
+            .ok_or(BoardError::UnknownBoard { id })
+    }
+
+    /// Remove the board with the given id from the registry, stopping it and freeing its cells.
+    /// Returns `false` if no board has that id.
+    pub fn remove(&self, id: Uuid) -> bool {
+        let Some((_, engine)) = self.boards.remove(&id) else {
+            return false;
+        };
+
+        // A command still using the board keeps it alive, so its cells are freed now rather than with the last reference
+        engine.board().dispose();
+
+        true
+    }
+}
@@ -0,0 +1,259 @@
+use crate::rule::Rule;
+use std::collections::HashSet;
+
+/// The maximum length of a line of an RLE body
+const MAX_LINE_LENGTH: usize = 70;
+
+/// The width, the height and the positions of the alive cells of a parsed pattern
+pub type ParsedPattern = (u64, u64, Vec<(u64, u64)>);
+
+/// Parse the header line of a pattern in the RLE format, e.g. `x = 3, y = 3, rule = B3/S23`.
+/// Returns the width and height of the pattern.
+fn parse_header(header: &str) -> Result<(u64, u64), String> {
+    let mut width = None;
+    let mut height = None;
+
+    for field in header.split(',') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid field `{}` in the RLE header", field.trim()))?;
+
+        let key = key.trim();
+        let value = value.trim();
+
+        match key {
+            "x" | "y" => {
+                let size = value.parse::<u64>().map_err(|_| {
+                    format!("Invalid value `{value}` for `{key}` in the RLE header")
+                })?;
+
+                if key == "x" {
+                    width = Some(size);
+                } else {
+                    height = Some(size);
+                }
+            }
+            // The other fields (e.g. the rule) do not change the cells of the pattern
+            _ => {}
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        (None, _) => Err("The RLE header is missing the `x` field".to_string()),
+        (_, None) => Err("The RLE header is missing the `y` field".to_string()),
+    }
+}
+
+/// Parse a pattern in the Run Length Encoded (RLE) format.
+/// Returns the width and height declared in the header and the positions of the alive cells,
+/// relative to the top left corner of the pattern.
+pub fn parse_rle(input: &str) -> Result<ParsedPattern, String> {
+    let mut lines = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let header = lines.next().ok_or("The RLE pattern is empty")?;
+
+    if !header.starts_with('x') {
+        return Err("The RLE pattern is missing the `x = .., y = ..` header".to_string());
+    }
+
+    let (width, height) = parse_header(header)?;
+
+    let mut cells = Vec::new();
+    let mut x: u64 = 0;
+    let mut y: u64 = 0;
+    let mut count: Option<u64> = None;
+    let mut terminated = false;
+
+    'body: for line in lines {
+        for token in line.chars() {
+            match token {
+                '0'..='9' => {
+                    let digit = u64::from(token as u8 - b'0');
+
+                    let run = count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|run| run.checked_add(digit))
+                        .ok_or("A run count in the RLE body is too large")?;
+
+                    count = Some(run);
+                }
+                'b' | 'o' => {
+                    let run = count.take().unwrap_or(1);
+
+                    if token == 'o' {
+                        if x.saturating_add(run) > width {
+                            return Err(format!(
+                                "Row {} is wider than the declared width of {width}",
+                                y + 1
+                            ));
+                        }
+
+                        if y >= height {
+                            return Err(format!(
+                                "The pattern has more rows than the declared height of {height}"
+                            ));
+                        }
+
+                        cells.extend((x..x + run).map(|x| (x, y)));
+                    }
+
+                    x = x.saturating_add(run);
+                }
+                '$' => {
+                    y = y.saturating_add(count.take().unwrap_or(1));
+                    x = 0;
+                }
+                '!' => {
+                    terminated = true;
+                    break 'body;
+                }
+                token if token.is_whitespace() => {}
+                token => {
+                    return Err(format!("Unexpected character `{token}` in the RLE body"));
+                }
+            }
+        }
+    }
+
+    if !terminated {
+        return Err("The RLE pattern is missing the `!` terminator".to_string());
+    }
+
+    if count.is_some() {
+        return Err("The RLE body ends with a run count without a tag".to_string());
+    }
+
+    Ok((width, height, cells))
+}
+
+/// Format a run of `count` cells with the given tag, e.g. `3o`
+fn format_run(count: u64, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{count}{tag}")
+    }
+}
+
+/// Write a pattern in the Run Length Encoded (RLE) format.
+/// The positions of the alive cells are relative to the top left corner of the pattern,
+/// and the ones outside of the given width and height are ignored.
+pub fn write_rle(width: u64, height: u64, cells: &[(u64, u64)], rule: &Rule) -> String {
+    let alive: HashSet<(u64, u64)> = cells.iter().copied().collect();
+
+    let mut tokens = Vec::new();
+    let mut last_row = None;
+
+    for y in 0..height {
+        let mut row = Vec::new();
+        let mut x = 0;
+
+        while x < width {
+            let state = alive.contains(&(x, y));
+            let start = x;
+
+            while x < width && alive.contains(&(x, y)) == state {
+                x += 1;
+            }
+
+            row.push((x - start, if state { 'o' } else { 'b' }));
+        }
+
+        // The dead cells at the end of a row are implicit
+        if row.last().is_some_and(|&(_, tag)| tag == 'b') {
+            row.pop();
+        }
+
+        if row.is_empty() {
+            continue;
+        }
+
+        if let Some(last_row) = last_row {
+            tokens.push(format_run(y - last_row, '$'));
+        }
+
+        tokens.extend(row.into_iter().map(|(count, tag)| format_run(count, tag)));
+
+        last_row = Some(y);
+    }
+
+    tokens.push("!".to_string());
+
+    let mut body = String::new();
+    let mut line_length = 0;
+
+    for token in tokens {
+        if line_length + token.len() > MAX_LINE_LENGTH {
+            body.push('\n');
+            line_length = 0;
+        }
+
+        line_length += token.len();
+        body.push_str(&token);
+    }
+
+    format!("x = {width}, y = {height}, rule = {rule}\n{body}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_glider() {
+        let glider = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!";
+
+        assert_eq!(
+            parse_rle(glider),
+            Ok((3, 3, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]))
+        );
+    }
+
+    #[test]
+    fn reject_malformed_patterns() {
+        let malformed = [
+            ("", "The RLE pattern is empty"),
+            (
+                "bo$2bo$3o!",
+                "The RLE pattern is missing the `x = .., y = ..` header",
+            ),
+            (
+                "x = 3\nbo$2bo$3o!",
+                "The RLE header is missing the `y` field",
+            ),
+            (
+                "x = 3, y = three\nbo!",
+                "Invalid value `three` for `y` in the RLE header",
+            ),
+            (
+                "x = 3, y = 3\nbo$2bo$3o",
+                "The RLE pattern is missing the `!` terminator",
+            ),
+            (
+                "x = 3, y = 3\nbo$2bo$4o!",
+                "Row 3 is wider than the declared width of 3",
+            ),
+            (
+                "x = 3, y = 2\nbo$2bo$3o!",
+                "The pattern has more rows than the declared height of 2",
+            ),
+            (
+                "x = 3, y = 3\nbo$2bx$3o!",
+                "Unexpected character `x` in the RLE body",
+            ),
+            (
+                "x = 3, y = 3\nbo$2bo$3o3!",
+                "The RLE body ends with a run count without a tag",
+            ),
+        ];
+
+        for (input, error) in malformed {
+            assert_eq!(parse_rle(input), Err(error.to_string()), "{input:?}");
+        }
+    }
+}
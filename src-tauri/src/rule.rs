@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A struct representing the birth/survival rule of the automaton, e.g. B3/S23 for Conway's Game of Life
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rule {
+    /// The numbers of alive neighbors for which a dead cell becomes alive
+    pub birth: Vec<u8>,
+
+    /// The numbers of alive neighbors for which an alive cell stays alive
+    pub survival: Vec<u8>,
+}
+
+impl Default for Rule {
+    /// Conway's Game of Life, B3/S23
+    fn default() -> Self {
+        Self {
+            birth: vec![3],
+            survival: vec![2, 3],
+        }
+    }
+}
+
+impl Rule {
+    /// Create a new rule
+    pub fn new(birth: Vec<u8>, survival: Vec<u8>) -> Self {
+        Self { birth, survival }
+    }
+
+    /// Parse a rule in the B/S notation, e.g. `B3/S23`, as copied from the LifeWiki.
+    /// The letters may be lowercase and the two parts in either order, and a part may have no digit, e.g. `B2/S`.
+    /// Returns an error if a part is missing or if a neighbor count is not a digit between 0 and 8.
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid rule `{rule}`, expected the B/S notation, e.g. `B3/S23`");
+
+        let (first, second) = rule.trim().split_once('/').ok_or_else(invalid)?;
+
+        let mut birth = None;
+        let mut survival = None;
+
+        for part in [first, second] {
+            let mut chars = part.chars();
+
+            let counts = match chars.next().map(|letter| letter.to_ascii_uppercase()) {
+                Some('B') if birth.is_none() => &mut birth,
+                Some('S') if survival.is_none() => &mut survival,
+                _ => return Err(invalid()),
+            };
+
+            *counts = Some(
+                chars
+                    .map(|digit| match digit.to_digit(10) {
+                        Some(count) if count <= 8 => Ok(count as u8),
+                        _ => Err(format!(
+                            "Invalid neighbor count `{digit}` in the rule `{rule}`, it must be a digit between 0 and 8"
+                        )),
+                    })
+                    .collect::<Result<Vec<u8>, String>>()?,
+            );
+        }
+
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(Self::new(birth, survival)),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Check that every neighbor count of the rule can be reached by a cell with `max_neighbors` neighbors
+    pub fn validate(&self, max_neighbors: usize) -> Result<(), String> {
+        match self
+            .birth
+            .iter()
+            .chain(&self.survival)
+            .find(|&&count| count as usize > max_neighbors)
+        {
+            Some(count) => Err(format!(
+                "Invalid neighbor count {count} in the rule, it must be between 0 and {max_neighbors}"
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Compute the future state of a cell from its current state and its number of alive neighbors
+    pub fn next_state(&self, alive: bool, alive_neighbors: usize) -> bool {
+        let counts = if alive { &self.survival } else { &self.birth };
+
+        counts
+            .iter()
+            .any(|&count| count as usize == alive_neighbors)
+    }
+}
+
+impl fmt::Display for Rule {
+    /// Format the rule in the B/S notation, e.g. `B36/S23`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |counts: &[u8]| {
+            let mut counts = counts.to_vec();
+
+            counts.sort_unstable();
+            counts.dedup();
+
+            counts.iter().map(u8::to_string).collect::<String>()
+        };
+
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
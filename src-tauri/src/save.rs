@@ -0,0 +1,47 @@
+use crate::config::{BoundaryMode, NeighborMask};
+use crate::rule::Rule;
+use serde::{Deserialize, Serialize};
+
+/// A struct representing a board saved to a file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedBoard {
+    /// The width of the board
+    pub width: u64,
+
+    /// The height of the board
+    pub height: u64,
+
+    /// The generation of the board
+    pub generation: usize,
+
+    /// The positions of the alive cells
+    pub alive: Vec<(u64, u64)>,
+}
+
+/// A struct representing the whole state of a board, with the settings needed to run it again identically
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardSnapshot {
+    /// The width of the board
+    pub width: u64,
+
+    /// The height of the board
+    pub height: u64,
+
+    /// The generation of the board
+    pub generation: usize,
+
+    /// What lies beyond the edges of the board
+    pub boundary: BoundaryMode,
+
+    /// The birth/survival rule of the board
+    pub rule: Rule,
+
+    /// The radius of the neighborhood of a cell
+    pub radius: usize,
+
+    /// Which of the cells within the neighborhood radius are the neighbors of a cell
+    pub mask: NeighborMask,
+
+    /// The positions of the alive cells, sorted by `(x, y)`
+    pub alive: Vec<(u64, u64)>,
+}
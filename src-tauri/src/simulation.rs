@@ -0,0 +1,169 @@
+use crate::cell::Cell;
+use crate::config::BoundaryMode;
+use crate::rule::Rule;
+use std::collections::{HashMap, HashSet};
+
+/// The offsets of the eight neighbors of a cell
+pub const NEIGHBOR_OFFSETS: [(i64, i64); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// The largest supported neighborhood radius, so that every neighbor count fits in the `u8` of a rule
+pub const MAX_NEIGHBOR_RADIUS: usize = 7;
+
+/// Get the offsets of the neighbors of a cell within the given radius, i.e. over `[-radius, radius]²`
+/// without the cell itself. A radius of 1 gives the eight neighbors of `NEIGHBOR_OFFSETS`.
+pub fn neighbor_offsets(radius: usize) -> Vec<(i64, i64)> {
+    let radius = radius as i64;
+
+    (-radius..=radius)
+        .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+        .filter(|&offset| offset != (0, 0))
+        .collect()
+}
+
+/// Get the number of neighbors of a cell within the given radius
+pub fn neighbor_count(radius: usize) -> usize {
+    (2 * radius + 1).pow(2) - 1
+}
+
+/// Wrap a coordinate beyond the edges of a side of the given length back onto it.
+/// Returns `None` if the side is too long for the `i64` type.
+fn wrap(position: u64, offset: i64, length: u64) -> Option<u64> {
+    let length = i64::try_from(length).ok()?;
+    let position = Cell::offset_position_signed(position, offset)?;
+
+    u64::try_from(position.rem_euclid(length)).ok()
+}
+
+/// Reflect a coordinate beyond the edges of a side of the given length back onto it,
+/// as if the side was repeated in mirror images.
+/// Returns `None` if twice the side is too long for the `i64` type.
+fn reflect(position: u64, offset: i64, length: u64) -> Option<u64> {
+    let period = i64::try_from(length).ok()?.checked_mul(2)?;
+    let position = Cell::offset_position_signed(position, offset)?.rem_euclid(period);
+
+    u64::try_from(if position < period / 2 {
+        position
+    } else {
+        period - 1 - position
+    })
+    .ok()
+}
+
+/// Get the position of the neighbor at the given offset of a cell.
+/// With reflective edges the neighbor beyond an edge is its reflection on the board, which can be the cell itself.
+/// Returns `None` if the neighbor is outside the board, or if it is the cell itself after wrapping around.
+/// The wrapped and reflected positions are computed with checked arithmetic, so that they cannot overflow silently
+/// on a side too long for the `i64` type, in which case `None` is returned too.
+pub fn neighbor_position(
+    x: u64,
+    y: u64,
+    dx: i64,
+    dy: i64,
+    width: u64,
+    height: u64,
+    boundary: BoundaryMode,
+) -> Option<(u64, u64)> {
+    match boundary {
+        BoundaryMode::Dead => {
+            let nx = Cell::offset_position(x, dx)?;
+            let ny = Cell::offset_position(y, dy)?;
+
+            (nx < width && ny < height).then_some((nx, ny))
+        }
+        BoundaryMode::Toroidal => {
+            let nx = wrap(x, dx, width)?;
+            let ny = wrap(y, dy, height)?;
+
+            // On a board narrower than the neighborhood, a cell could wrap around onto itself
+            ((nx, ny) != (x, y)).then_some((nx, ny))
+        }
+        BoundaryMode::Reflective => Some((reflect(x, dx, width)?, reflect(y, dy, height)?)),
+    }
+}
+
+/// Get the positions of the neighbors of a cell at the given offsets.
+/// A neighbor reached by several offsets on a small toroidal board is listed once,
+/// while with reflective edges each reflection of a cell counts as a neighbor.
+pub fn neighbor_positions(
+    x: u64,
+    y: u64,
+    offsets: &[(i64, i64)],
+    width: u64,
+    height: u64,
+    boundary: BoundaryMode,
+) -> Vec<(u64, u64)> {
+    let mut neighbors: Vec<(u64, u64)> = offsets
+        .iter()
+        .filter_map(|&(dx, dy)| neighbor_position(x, y, dx, dy, width, height, boundary))
+        .collect();
+
+    if boundary == BoundaryMode::Toroidal {
+        neighbors.sort_unstable();
+        neighbors.dedup();
+    }
+
+    neighbors
+}
+
+/// Compute the next generation of a set of alive cells on a board of the given size.
+/// Returns the set of the positions of the alive cells of the next generation.
+pub fn step(
+    cells: &HashSet<(u64, u64)>,
+    width: u64,
+    height: u64,
+    boundary: BoundaryMode,
+    rule: &Rule,
+) -> HashSet<(u64, u64)> {
+    step_with_offsets(cells, width, height, boundary, rule, &NEIGHBOR_OFFSETS)
+}
+
+/// Compute the next generation of a set of alive cells, like `step`,
+/// with the neighbors of a cell at the given offsets, e.g. from `neighbor_offsets`.
+/// Under a rule with B0, the dead cells without alive neighbors are born, which goes through the whole board.
+pub fn step_with_offsets(
+    cells: &HashSet<(u64, u64)>,
+    width: u64,
+    height: u64,
+    boundary: BoundaryMode,
+    rule: &Rule,
+    offsets: &[(i64, i64)],
+) -> HashSet<(u64, u64)> {
+    let mut alive_neighbors: HashMap<(u64, u64), usize> = HashMap::new();
+
+    for &(x, y) in cells.iter().filter(|&&(x, y)| x < width && y < height) {
+        for neighbor in neighbor_positions(x, y, offsets, width, height, boundary) {
+            *alive_neighbors.entry(neighbor).or_insert(0) += 1;
+        }
+    }
+
+    let isolated = cells.iter().filter(|&&(x, y)| {
+        x < width
+            && y < height
+            && !alive_neighbors.contains_key(&(x, y))
+            && rule.next_state(true, 0)
+    });
+
+    let born_alone = rule
+        .next_state(false, 0)
+        .then(|| (0..height).flat_map(move |y| (0..width).map(move |x| (x, y))))
+        .into_iter()
+        .flatten()
+        .filter(|position| !cells.contains(position) && !alive_neighbors.contains_key(position));
+
+    alive_neighbors
+        .iter()
+        .filter(|&(position, &count)| rule.next_state(cells.contains(position), count))
+        .map(|(&position, _)| position)
+        .chain(isolated.copied())
+        .chain(born_alone)
+        .collect()
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// A struct representing the statistics of a board, computed at once for an info panel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardStats {
+    /// The number of alive cells
+    pub population: usize,
+
+    /// The generation of the board
+    pub generation: usize,
+
+    /// The ratio of alive cells to the number of cells, `0.0` on an empty board
+    pub density: f64,
+
+    /// The bounding box of the alive cells as `(min_x, min_y, max_x, max_y)`, `None` if no cell is alive
+    pub bounding_box: Option<(u64, u64, u64, u64)>,
+
+    /// The largest Chebyshev distance from the center of the bounding box to an alive cell, to measure the spread of a pattern.
+    /// It is a multiple of 0.5, the center being between two cells for an even size, and `0.0` on an empty board.
+    pub radius: f64,
+}
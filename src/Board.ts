@@ -6,6 +6,8 @@ export default class Board {
 
     public readonly board: HTMLDivElement;
 
+    private _boardId: string | null = null;
+
     private _running: boolean = false;
 
     private _sleepTime: number = 100;
@@ -38,6 +40,8 @@ export default class Board {
             if (event.buttons === 1) {
                 isMouseDown = true;
 
+                await this.beginEdit();
+
                 const cell: Cell | undefined = this._cells.find(
                     (cell: Cell) => cell === event.target,
                 );
@@ -54,8 +58,10 @@ export default class Board {
             }
         });
 
-        this.board.addEventListener("mouseup", () => {
+        this.board.addEventListener("mouseup", async () => {
             isMouseDown = false;
+
+            await this.endEdit();
         });
 
         this.board.addEventListener("mouseover", async (event: MouseEvent) => {
@@ -75,19 +81,42 @@ export default class Board {
             }
         });
 
-        this.board.addEventListener("mouseenter", (event: MouseEvent) => {
+        this.board.addEventListener("mouseenter", async (event: MouseEvent) => {
             if (event.buttons === 1) {
                 isMouseDown = true;
+
+                await this.beginEdit();
             }
         });
 
-        this.board.addEventListener("mouseleave", () => {
-            isMouseDown = false;
+        this.board.addEventListener("mouseleave", async () => {
+            if (isMouseDown) {
+                isMouseDown = false;
+
+                await this.endEdit();
+            }
+        });
+    }
+
+    // The generations are skipped by the backend between these two calls,
+    // so that a stroke of the mouse is never mixed with a generation
+    private async beginEdit(): Promise<void> {
+        await invoke("begin_edit", { boardId: this._boardId }).catch(
+            (error) => {
+                console.error(error);
+            },
+        );
+    }
+
+    private async endEdit(): Promise<void> {
+        await invoke("end_edit", { boardId: this._boardId }).catch((error) => {
+            console.error(error);
         });
     }
 
     private async updateCellState(cell: Cell, state: boolean): Promise<void> {
         await invoke("update_cell_state", {
+            boardId: this._boardId,
             id: cell.id,
             newState: state,
         })
@@ -199,7 +228,12 @@ export default class Board {
 
         let createdCells: [string, number, number][] = [];
 
+        if (this._boardId === null) {
+            this._boardId = (await invoke("new_board")) as string;
+        }
+
         await invoke("create_board", {
+            boardId: this._boardId,
             width: this._width,
             height: this._height,
         })
@@ -236,7 +270,9 @@ export default class Board {
     }
 
     public async killBoard(): Promise<void> {
-        await invoke("kill_board");
+        await invoke("kill_board", {
+            boardId: this._boardId,
+        });
 
         this._cells.forEach((cell: Cell) => {
             cell.alive = false;
@@ -246,9 +282,28 @@ export default class Board {
     public async step(): Promise<void> {
         let status: [string, boolean][] = [];
 
-        await invoke("compute_next_gen")
-            .then((response) => {
-                status = response as [string, boolean][];
+        // The diff is null while the cells are being edited, there is nothing to commit
+        const diff: GenerationDiff | null = await invoke("compute_next_gen", {
+            boardId: this._boardId,
+        })
+            .then((response) => response as GenerationDiff | null)
+            .catch((error) => {
+                console.error(error);
+
+                return null;
+            });
+
+        if (!diff) {
+            return;
+        }
+
+        // The backend rejects the diff if the board changed generation or the cells were edited since it was computed
+        await invoke("commit_generation", {
+            boardId: this._boardId,
+            diff,
+        })
+            .then(() => {
+                status = diff.changes;
             })
             .catch((error) => {
                 console.error(error);
@@ -294,6 +349,12 @@ export type Size = {
     height: number;
 };
 
+interface GenerationDiff {
+    generation: number;
+    version: number;
+    changes: [string, boolean][];
+}
+
 export enum DrawType {
     Dead,
     Alive,